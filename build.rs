@@ -0,0 +1,43 @@
+//! Generates the PT100 temperature lookup table used by `temp_conversion`
+//!
+//! The resistance of each entry is computed from the Callendar-Van Dusen equation
+//! with the IEC 60751 coefficients, so the table is consistent with the floating
+//! point conversions. Adjust the range and step below to change the resolution.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+// the temperature range of the table in degrees Celcius
+const TABLE_MIN: i32 = -200;
+const TABLE_MAX: i32 = 850;
+const TABLE_STEP: i32 = 10;
+
+// the nominal resistance of the element at 0 degrees Celcius, the driver scales
+// a PT1000 down to the PT100 range so this normally stays at 100 Ohms
+const R0: f64 = 100.0;
+
+// Callendar-Van Dusen coefficients as specified by IEC 60751
+const CVD_A: f64 = 3.9083e-3;
+const CVD_B: f64 = -5.775e-7;
+const CVD_C: f64 = -4.183e-12;
+
+fn resistance(celsius: f64) -> f64 {
+    let t2 = celsius * celsius;
+    let c = if celsius < 0.0 { CVD_C * (celsius - 100.0) * t2 * celsius } else { 0.0 };
+    R0 * (1.0 + CVD_A * celsius + CVD_B * t2 + c)
+}
+
+fn main() {
+    let mut table = String::from("static LOOKUP_TABLE : &[TempPair] = &[\n");
+    for temp in (TABLE_MIN..=TABLE_MAX).step_by(TABLE_STEP as usize) {
+        let ohms = (resistance(temp as f64) * 100.0).round() as u16;
+        writeln!(table, "    ({}, {}),", temp * 100, ohms).unwrap();
+    }
+    table.push_str("];\n");
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("lookup_table.rs");
+    fs::write(out, table).unwrap();
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -0,0 +1,62 @@
+//! Reading two MAX31865 chips sharing one SPI bus on a Raspberry Pi
+//!
+//! The chip select lines are driven as GPIOs and handed to `embedded-hal-bus`,
+//! which turns the single bus into one `SpiDevice` per chip. The bus is opened
+//! with `SPI_NO_CS` so the kernel doesn't drive its own chip select line.
+
+use std::cell::RefCell;
+
+use embedded_hal::digital::PinState;
+use embedded_hal_bus::spi::RefCellDevice;
+use linux_embedded_hal::spidev::{SpiModeFlags, SpidevOptions};
+use linux_embedded_hal::{Delay, SpidevBus, SysfsPin};
+use max31865::{FilterMode, Max31865Builder, SensorType};
+
+fn output_pin(num: u64) -> SysfsPin {
+    let pin = SysfsPin::new(num);
+    pin.export().expect("failed to export GPIO");
+    pin.into_output_pin(PinState::High).expect("failed to configure GPIO")
+}
+
+fn input_pin(num: u64) -> SysfsPin {
+    let pin = SysfsPin::new(num);
+    pin.export().expect("failed to export GPIO");
+    pin.into_input_pin().expect("failed to configure GPIO")
+}
+
+fn main() {
+    let mut spi = SpidevBus::open("/dev/spidev0.0").expect("failed to open SPI bus");
+    let options = SpidevOptions::new()
+        .max_speed_hz(1_000_000)
+        .mode(SpiModeFlags::SPI_MODE_3 | SpiModeFlags::SPI_NO_CS)
+        .build();
+    spi.configure(&options).expect("failed to configure SPI bus");
+    let bus = RefCell::new(spi);
+
+    let mut sensors = [(8, 24), (7, 25)].map(|(ncs, rdy)| {
+        let spi = RefCellDevice::new(&bus, output_pin(ncs), Delay)
+            .expect("failed to set chip select high");
+
+        Max31865Builder::new()
+            .vbias(true)
+            .conversion_mode(true)
+            .sensor(SensorType::ThreeWire)
+            .filter(FilterMode::Filter50Hz)
+            .build(spi, input_pin(rdy))
+            .expect("failed to configure MAX31865")
+    });
+
+    loop {
+        for (channel, sensor) in sensors.iter_mut().enumerate() {
+            if sensor.wait_for_ready(100_000).is_err() {
+                println!("channel {}: conversion timed out", channel);
+                continue;
+            }
+
+            match sensor.read_default_conversion() {
+                Ok(temp) => println!("channel {}: {:.2} C", channel, temp as f32 / 100.0),
+                Err(_) => println!("channel {}: read failed", channel),
+            }
+        }
+    }
+}
@@ -0,0 +1,1531 @@
+//! An async variant of the driver using `embedded-hal-async`
+//!
+//! This mirrors the blocking `Max31865` driver, but all bus accesses are awaited
+//! and the ready pin is awaited through the `Wait` trait instead of being polled.
+//! The register layout and conversion logic are shared with the blocking driver.
+//! Requires the `async` feature.
+
+use core::future::{poll_fn, Future};
+use core::pin::pin;
+use core::task::Poll;
+
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+use embedded_hal_async::spi::SpiDevice;
+use hal::digital::InputPin;
+use hal::spi::Mode;
+
+use crate::{
+    compute_calibration, ratio_to_raw, raw_to_ratio, sensor_connected, wiring_for_config,
+    AlertState, Calibration, Config, ConfigBits, ConversionParams, DeviceState, Error,
+    FaultStatus, FilterMode, NoReady, Reading, ReferenceResistance, Register, Rounding,
+    RtdClass, RtdReading, RtdType, Wiring, CONFIG_SETTINGS, CONNECTION_PATTERNS,
+    FAULT_DETECTION_POLLS, FAULT_DETECTION_POLL_US, MODE, MODE_1, MODE_3, R, READINGS_POLLS,
+    READINGS_POLL_US, VBIAS_SETTLE_TIME_US, W,
+};
+
+/// Async MAX31865 driver, see `crate::Max31865` for the blocking version and `R0`.
+#[derive(Debug)]
+pub struct Max31865<SPI, RDY = NoReady, const R0: u32 = 0> {
+    spi: SPI,
+    rdy: RDY,
+    config: u8,
+    wiring: Wiring,
+    params: ConversionParams,
+    channel: u8,
+    last_raw: Option<u16>,
+    sample_count: u8,
+    one_shot_pending: bool,
+    mode: Mode,
+}
+
+/// An async driver fixed to a PT100 element, see `crate::Max31865Pt100`.
+pub type Max31865Pt100<SPI, RDY = NoReady> = Max31865<SPI, RDY, 100>;
+
+/// An async driver fixed to a PT1000 element, see `crate::Max31865Pt1000`.
+pub type Max31865Pt1000<SPI, RDY = NoReady> = Max31865<SPI, RDY, 1000>;
+
+impl<E, SPI> Max31865<SPI, NoReady>
+where
+    SPI: SpiDevice<u8, Error = E>,
+{
+    /// Create a new async MAX31865 module without a ready pin.
+    pub fn new_without_ready(spi: SPI) -> Result<Max31865<SPI, NoReady>, Error<E>> {
+        Max31865::new(spi, NoReady)
+    }
+}
+
+impl<E, SPI, RDY> Max31865<SPI, RDY>
+where
+    SPI: SpiDevice<u8, Error = E>,
+{
+    /// Create a new async MAX31865 module, see `crate::Max31865::new`.
+    pub fn new(spi: SPI, rdy: RDY) -> Result<Max31865<SPI, RDY>, Error<E>> {
+        let max31865 = Max31865 {
+            spi,
+            rdy,
+            config: 0, /* power-on reset value of the configuration register */
+            wiring: Wiring::TwoWire,
+            params: ConversionParams::new(RtdType::Pt100),
+            channel: 0,
+            last_raw: None,
+            sample_count: 1,
+            one_shot_pending: false,
+            mode: MODE,
+        };
+
+        Ok(max31865)
+    }
+
+    /// Create a new async MAX31865 module and record the SPI mode, see
+    /// `crate::Max31865::new_with_mode`.
+    pub fn new_with_mode(spi: SPI, rdy: RDY, mode: Mode) -> Result<Max31865<SPI, RDY>, Error<E>> {
+        if mode != MODE_1 && mode != MODE_3 {
+            return Err(Error::UnsupportedMode);
+        }
+        let mut max31865 = Max31865::new(spi, rdy)?;
+        max31865.mode = mode;
+
+        Ok(max31865)
+    }
+
+    /// Create a new async MAX31865 module for a PT100 element, see
+    /// `crate::Max31865::new_pt100`.
+    pub fn new_pt100(spi: SPI, rdy: RDY) -> Result<Max31865Pt100<SPI, RDY>, Error<E>> {
+        Ok(Max31865::new(spi, rdy)?.into_fixed())
+    }
+
+    /// Create a new async MAX31865 module for a PT1000 element, see
+    /// `crate::Max31865::new_pt1000`.
+    pub fn new_pt1000(spi: SPI, rdy: RDY) -> Result<Max31865Pt1000<SPI, RDY>, Error<E>> {
+        let mut max31865 = Max31865::new(spi, rdy)?;
+        max31865.set_rtd_type(RtdType::Pt1000);
+
+        Ok(max31865.into_fixed())
+    }
+
+    /// Create a new async MAX31865 module with a known calibration value, see
+    /// `crate::Max31865::new_with_calibration`.
+    pub fn new_with_calibration(spi: SPI, rdy: RDY, calib: Calibration)
+        -> Result<Max31865<SPI, RDY>, Error<E>> {
+        let mut max31865 = Max31865::new(spi, rdy)?;
+        max31865.params = ConversionParams::with_calibration(RtdType::Pt100, calib.centi_ohms());
+
+        Ok(max31865)
+    }
+
+    /// Set the type of RTD element, see `crate::Max31865::set_rtd_type`.
+    pub fn set_rtd_type(&mut self, rtd_type: RtdType) {
+        self.params = ConversionParams::new(rtd_type).with_overrides_of(&self.params);
+    }
+
+    /// Fix the RTD type of the driver in its type, see `Max31865Pt100`.
+    fn into_fixed<const T: u32>(self) -> Max31865<SPI, RDY, T> {
+        let Max31865 { spi, rdy, config, wiring, params, channel, last_raw, sample_count,
+            one_shot_pending, mode } = self;
+        Max31865 { spi, rdy, config, wiring, params, channel, last_raw, sample_count,
+            one_shot_pending, mode }
+    }
+}
+
+impl<E, SPI, RDY, const R0: u32> Max31865<SPI, RDY, R0>
+where
+    SPI: SpiDevice<u8, Error = E>,
+{
+    /// The SPI mode passed to `new_with_mode`, see `crate::Max31865::mode`.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// The ready pin passed to `new`, see `crate::Max31865::ready_pin`.
+    pub fn ready_pin(&self) -> &RDY {
+        &self.rdy
+    }
+
+    /// Mutable access to the ready pin, see `crate::Max31865::ready_pin`.
+    pub fn ready_pin_mut(&mut self) -> &mut RDY {
+        &mut self.rdy
+    }
+
+    /// Updates the devices configuration, see `crate::Max31865::configure_with`.
+    pub async fn configure_with(&mut self, config: Config) -> Result<(), Error<E>> {
+        if self.config & (1 << 6) != 0 && (self.config ^ config.to_bits()) & 1 != 0 {
+            return Err(Error::FilterChange);
+        }
+
+        self.write(Register::CONFIG, config.to_bits()).await?;
+        self.cache_config(config.to_bits());
+        Ok(())
+    }
+
+    /// Configure the device with `Config::default`, see `crate::Max31865::configure_default`.
+    pub async fn configure_default(&mut self) -> Result<(), Error<E>> {
+        self.configure_with(Config::default()).await
+    }
+
+    /// Change the filter mode, preserving all other settings, see
+    /// `crate::Max31865::set_filter`.
+    pub async fn set_filter(&mut self, filter_mode: FilterMode) -> Result<(), Error<E>> {
+        let auto = self.config & (1 << 6);
+        if auto != 0 {
+            self.modify_config(1 << 6, 0).await?;
+        }
+        self.modify_config(1, filter_mode.to_bit() as u8).await?;
+        if auto != 0 {
+            self.modify_config(0, auto).await?;
+        }
+        Ok(())
+    }
+
+    /// Start automatic conversion with the given filter mode, see
+    /// `crate::Max31865::start_continuous`.
+    pub async fn start_continuous(&mut self, filter_mode: FilterMode) -> Result<(), Error<E>> {
+        if self.config & (1 << 6) != 0 {
+            self.modify_config(1 << 6, 0).await?;
+        }
+        self.modify_config(1, (1 << 7) | filter_mode.to_bit() as u8).await?;
+        self.modify_config(0, 1 << 6).await?;
+        Ok(())
+    }
+
+    /// Stop automatic conversion, leaving V_BIAS enabled.
+    pub async fn stop_continuous(&mut self) -> Result<(), Error<E>> {
+        self.modify_config(1 << 6, 0).await?;
+        Ok(())
+    }
+
+    /// Set the physical wiring of the sensor, see `crate::Max31865::set_wiring`.
+    pub async fn set_wiring(&mut self, wiring: Wiring) -> Result<(), Error<E>> {
+        self.wiring = wiring;
+        self.modify_config(1 << 4, (wiring.sensor_type() as u8) << 4).await?;
+        Ok(())
+    }
+
+    /// The physical wiring of the sensor, see `crate::Max31865::wiring`.
+    pub fn wiring(&self) -> Wiring {
+        self.wiring
+    }
+
+    /// Read back and decode the configuration register.
+    pub async fn read_config(&mut self) -> Result<Config, Error<E>> {
+        let bits = self.read(Register::CONFIG).await?;
+        Ok(Config::from_bits(bits))
+    }
+
+    /// Read the configuration register without decoding it, see
+    /// `crate::Max31865::read_config_raw`.
+    pub async fn read_config_raw(&mut self) -> Result<u8, Error<E>> {
+        Ok(self.read(Register::CONFIG).await?)
+    }
+
+    /// Read the configuration register and decode every bit, see
+    /// `crate::Max31865::read_config_bits`.
+    pub async fn read_config_bits(&mut self) -> Result<ConfigBits, Error<E>> {
+        let bits = self.read_config_raw().await?;
+        Ok(ConfigBits::from_bits(bits))
+    }
+
+    /// Write the configuration register without any checks, see
+    /// `crate::Max31865::write_config_raw`.
+    pub async fn write_config_raw(&mut self, bits: u8) -> Result<(), Error<E>> {
+        self.write(Register::CONFIG, bits).await?;
+        self.cache_config(bits);
+        Ok(())
+    }
+
+    /// Determine whether a one shot conversion is still in progress, see
+    /// `crate::Max31865::conversion_in_progress`.
+    pub async fn conversion_in_progress(&mut self) -> Result<bool, Error<E>> {
+        Ok(self.read(Register::CONFIG).await? & (1 << 5) != 0)
+    }
+
+    /// The last configuration written to the device, see `crate::Max31865::config`.
+    pub fn config(&self) -> Config {
+        Config::from_bits(self.config)
+    }
+
+    /// Re-read the configuration register and update the cached configuration.
+    pub async fn refresh_config(&mut self) -> Result<Config, Error<E>> {
+        let bits = self.read(Register::CONFIG).await?;
+        self.cache_config(bits);
+        Ok(Config::from_bits(bits))
+    }
+
+    /// Check the configuration register against the cached configuration, see
+    /// `crate::Max31865::verify_config`.
+    pub async fn verify_config(&mut self) -> Result<bool, Error<E>> {
+        let bits = self.read(Register::CONFIG).await?;
+        Ok(bits & CONFIG_SETTINGS == self.config)
+    }
+
+    /// Set the resistance of the reference resistor in Ohms, see
+    /// `crate::Max31865::set_reference_resistance`.
+    pub fn set_reference_resistance(&mut self, ohms: ReferenceResistance) {
+        self.params = ConversionParams::with_reference_resistance(self.params.rtd_type, ohms.0)
+            .with_overrides_of(&self.params);
+    }
+
+    /// The resistance of the reference resistor in Ohms, see
+    /// `crate::Max31865::reference_resistance`.
+    pub fn reference_resistance(&self) -> f32 {
+        self.params.reference_ohms
+    }
+
+    /// Set the calibration reference resistance, see `crate::Max31865::set_calibration`.
+    pub fn set_calibration(&mut self, calib: Calibration) -> Result<(), Error<E>> {
+        self.params = ConversionParams::with_calibration(self.params.rtd_type, calib.0)
+            .with_overrides_of(&self.params);
+        Ok(())
+    }
+
+    /// The temperature resolution in degrees Celcius at the current settings, see
+    /// `crate::Max31865::temperature_resolution_c`.
+    pub fn temperature_resolution_c(&self) -> f32 {
+        self.params.temperature_resolution_c()
+    }
+
+    /// Classify the connected element by its resistance at a known temperature, see
+    /// `crate::Max31865::classify_sensor`.
+    pub fn classify_sensor(&self, measured_ohms: f32, reference_temp_c: f32) -> RtdClass {
+        self.params.rtd_type.classify(measured_ohms, reference_temp_c)
+    }
+
+    /// The current calibration value, see `crate::Max31865::calibration`.
+    pub fn calibration(&self) -> u32 {
+        self.params.calibration
+    }
+
+    /// The parameters used to convert the RTD registers, see
+    /// `crate::Max31865::conversion_params`.
+    pub fn conversion_params(&self) -> ConversionParams {
+        self.params
+    }
+
+    /// Replace the parameters used to convert the RTD registers, see
+    /// `crate::Max31865::set_conversion_params`.
+    pub fn set_conversion_params(&mut self, params: ConversionParams) {
+        self.params = params;
+    }
+
+    /// Replace the built-in temperature conversion with a custom one, see
+    /// `crate::Max31865::set_conversion`.
+    pub fn set_conversion(&mut self, conversion: Option<fn(f32) -> f32>) {
+        self.params = self.params.with_conversion(conversion);
+    }
+
+    /// Set a fixed temperature offset trim in degrees Celcius multiplied by 100, see
+    /// `crate::Max31865::set_temperature_offset`.
+    pub fn set_temperature_offset(&mut self, centi_c: i32) {
+        self.params = self.params.with_temperature_offset(centi_c);
+    }
+
+    /// The temperature offset trim, see `crate::Max31865::temperature_offset`.
+    pub fn temperature_offset(&self) -> i32 {
+        self.params.offset
+    }
+
+    /// Set how the integer conversion rounds, see `crate::Max31865::set_rounding`.
+    pub fn set_rounding(&mut self, mode: Rounding) {
+        self.params = self.params.with_rounding(mode);
+    }
+
+    /// The rounding of the integer conversion, see `crate::Max31865::rounding`.
+    pub fn rounding(&self) -> Rounding {
+        self.params.rounding
+    }
+
+    /// Set the coefficients correcting the nonlinearity of the ADC, see
+    /// `crate::Max31865::set_adc_linearization`.
+    pub fn set_adc_linearization(&mut self, coeffs: Option<[f32; 3]>) {
+        self.params = self.params.with_adc_linearization(coeffs);
+    }
+
+    /// The coefficients of the ADC nonlinearity correction, see
+    /// `crate::Max31865::adc_linearization`.
+    pub fn adc_linearization(&self) -> Option<[f32; 3]> {
+        self.params.linearization
+    }
+
+    /// Set the channel included in each `Reading`, see `crate::Max31865::set_channel`.
+    pub fn set_channel(&mut self, channel: u8) {
+        self.channel = channel;
+    }
+
+    /// The channel included in each `Reading`, see `crate::Max31865::channel`.
+    pub fn channel(&self) -> u8 {
+        self.channel
+    }
+
+    /// Set the number of conversions averaged by `read_one_shot`, see
+    /// `crate::Max31865::set_sample_count`.
+    pub fn set_sample_count(&mut self, samples: u8) {
+        self.sample_count = samples.max(1);
+    }
+
+    /// The number of conversions averaged by `read_one_shot`.
+    pub fn sample_count(&self) -> u8 {
+        self.sample_count
+    }
+
+    /// Restore the default calibration for the configured RTD type.
+    pub fn reset_calibration(&mut self) {
+        self.params = ConversionParams::new(self.params.rtd_type)
+            .with_overrides_of(&self.params);
+    }
+
+    /// Calibrate the device assuming the sensor is currently at 100 degrees Celcius, see
+    /// `crate::Max31865::calibrate_at_100c`.
+    pub async fn calibrate_at_100c(&mut self) -> Result<u32, Error<E>> {
+        let ratio = self.read_rtd().await?;
+        let calib = compute_calibration(ratio_to_raw(ratio))
+            * (self.params.rtd_type.nominal_resistance() / 100);
+        self.set_calibration(Calibration(calib))?;
+
+        Ok(calib)
+    }
+
+    /// Correct the measured resistance using readings at two known temperatures, see
+    /// `crate::Max31865::set_two_point_calibration`.
+    pub fn set_two_point_calibration(&mut self, raw_low: u16, temp_low: f32, raw_high: u16,
+        temp_high: f32) -> Result<(), Error<E>> {
+        self.params = self.params.with_two_point(raw_low, temp_low, raw_high, temp_high)
+            .ok_or(Error::OutOfRange)?;
+        Ok(())
+    }
+
+    /// Read the raw resistance value and then perform conversion to degrees Celcius
+    /// multiplied by 100.
+    #[cfg(feature = "lookup-table")]
+    pub async fn read_default_conversion(&mut self) -> Result<i32, Error<E>> {
+        let ratio = self.read_rtd().await?;
+
+        Ok(self.params.ratio_to_temp(ratio))
+    }
+
+    /// Read the temperature in degrees Fahrenheit multiplied by 100, see
+    /// `crate::Max31865::read_default_conversion_f`.
+    #[cfg(feature = "lookup-table")]
+    pub async fn read_default_conversion_f(&mut self) -> Result<i32, Error<E>> {
+        let temp = self.read_default_conversion().await?;
+        Ok(temp * 9 / 5 + 3200)
+    }
+
+    /// Read the temperature in degrees Fahrenheit, see
+    /// `crate::Max31865::read_temperature_f`.
+    #[cfg(feature = "lookup-table")]
+    pub async fn read_temperature_f(&mut self) -> Result<f32, Error<E>> {
+        let temp = self.read_default_conversion().await?;
+        Ok(temp as f32 * 9.0 / 500.0 + 32.0)
+    }
+
+    /// Read the temperature in Kelvin, see `crate::Max31865::read_temperature_k`.
+    #[cfg(feature = "lookup-table")]
+    pub async fn read_temperature_k(&mut self) -> Result<f32, Error<E>> {
+        let temp = self.read_default_conversion().await?;
+        Ok(temp as f32 / 100.0 + 273.15)
+    }
+
+    /// Trigger a single conversion, wait for it to complete and return the converted
+    /// temperature, see `crate::Max31865::read_one_shot`.
+    #[cfg(feature = "lookup-table")]
+    pub async fn read_one_shot(&mut self, delay: &mut impl DelayNs) -> Result<i32, Error<E>> {
+        if self.sample_count > 1 {
+            return self.read_averaged(self.sample_count, delay).await;
+        }
+
+        self.single_one_shot(delay).await
+    }
+
+    // a single one shot conversion, regardless of the sample count
+    #[cfg(feature = "lookup-table")]
+    async fn single_one_shot(&mut self, delay: &mut impl DelayNs) -> Result<i32, Error<E>> {
+        if self.config & (1 << 7) == 0 {
+            self.modify_config(0, 1 << 7).await?;
+            delay.delay_us(VBIAS_SETTLE_TIME_US).await;
+        }
+
+        let conf = self.modify_config(0, 1 << 5).await?;
+        delay.delay_us(Config::from_bits(conf).filter_mode.conversion_time_us()).await;
+
+        self.read_default_conversion().await
+    }
+
+    /// Perform several one shot conversions and return the average temperature, see
+    /// `crate::Max31865::read_averaged`.
+    #[cfg(feature = "lookup-table")]
+    pub async fn read_averaged(&mut self, samples: u8, delay: &mut impl DelayNs)
+        -> Result<i32, Error<E>> {
+        let mut sum = 0;
+        let mut count = 0;
+        let mut fault = None;
+
+        for _ in 0..samples {
+            match self.single_one_shot(delay).await {
+                Ok(temp) => {
+                    sum += temp;
+                    count += 1;
+                }
+                Err(Error::Fault(status)) => {
+                    fault = Some(status);
+                    self.clear_faults().await?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        match (count, fault) {
+            (0, Some(status)) => Err(Error::Fault(status)),
+            (0, None) => Err(Error::OutOfRange),
+            _ => Ok(sum / count),
+        }
+    }
+
+    /// Fill a buffer with consecutive one shot conversions, see `crate::Max31865::fill`.
+    #[cfg(feature = "lookup-table")]
+    pub async fn fill(&mut self, buf: &mut [i32], delay: &mut impl DelayNs)
+        -> Result<usize, Error<E>> {
+        for (i, sample) in buf.iter_mut().enumerate() {
+            match self.single_one_shot(delay).await {
+                Ok(temp) => *sample = temp,
+                Err(Error::Fault(_)) if i > 0 => return Ok(i),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(buf.len())
+    }
+
+    /// Perform a complete measurement and return the temperature in degrees Celcius, see
+    /// `crate::Max31865::measure`.
+    pub async fn measure(&mut self, delay: &mut impl DelayNs) -> Result<f32, Error<E>> {
+        let ratio = self.measure_ratio(delay).await?;
+
+        Ok(self.params.reading(ratio_to_raw(ratio), self.channel).temperature_c)
+    }
+
+    /// Perform a complete measurement and return only the resistance in Ohms, see
+    /// `crate::Max31865::measure_resistance`.
+    pub async fn measure_resistance(&mut self, delay: &mut impl DelayNs)
+        -> Result<f32, Error<E>> {
+        let ratio = self.measure_ratio(delay).await?;
+
+        Ok(self.params.ratio_to_resistance(ratio))
+    }
+
+    /// Estimate the resistance of the leads of a three wire sensor, see
+    /// `crate::Max31865::measure_lead_resistance`.
+    pub async fn measure_lead_resistance(&mut self, delay: &mut impl DelayNs)
+        -> Result<f32, Error<E>>
+    {
+        let (sensor_type, wiring) = (self.config & (1 << 4), self.wiring);
+
+        let lead = match self.modify_config(0, 1 << 4).await {
+            Ok(_) => match self.measure_resistance(delay).await {
+                Ok(compensated) => match self.modify_config(1 << 4, 0).await {
+                    Ok(_) => self.measure_resistance(delay).await
+                        .map(|uncompensated| uncompensated - compensated),
+                    Err(e) => Err(Error::Spi(e)),
+                },
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(Error::Spi(e)),
+        };
+        let restored = self.modify_config(1 << 4, sensor_type).await;
+        self.wiring = wiring;
+        let lead = lead?;
+        restored?;
+
+        Ok(lead)
+    }
+
+    /// Run the fault detection cycle and only measure if it found no fault, see
+    /// `crate::Max31865::measure_verified`.
+    #[cfg(feature = "lookup-table")]
+    pub async fn measure_verified(&mut self, delay: &mut impl DelayNs) -> Result<i32, Error<E>> {
+        let status = self.run_fault_detection_auto(delay).await?;
+        if !status.is_ok() {
+            return Err(Error::Fault(status));
+        }
+
+        let ratio = self.measure_ratio(delay).await?;
+        Ok(self.params.ratio_to_temp(ratio))
+    }
+
+    /// Perform a complete measurement while also controlling an external bias switch,
+    /// see `crate::Max31865::measure_with_bias`.
+    pub async fn measure_with_bias(&mut self, delay: &mut impl DelayNs, internal_vbias: bool,
+        bias: &mut impl FnMut(bool)) -> Result<f32, Error<E>>
+    {
+        let ratio = self.measure_ratio_with(delay, internal_vbias, bias).await?;
+
+        Ok(self.params.reading(ratio_to_raw(ratio), self.channel).temperature_c)
+    }
+
+    async fn measure_ratio(&mut self, delay: &mut impl DelayNs) -> Result<u16, Error<E>> {
+        self.measure_ratio_with(delay, true, &mut |_| {}).await
+    }
+
+    async fn measure_ratio_with(&mut self, delay: &mut impl DelayNs, internal_vbias: bool,
+        bias: &mut impl FnMut(bool)) -> Result<u16, Error<E>>
+    {
+        self.modify_config(1 << 6, (internal_vbias as u8) << 7).await?;
+        bias(true);
+        delay.delay_us(VBIAS_SETTLE_TIME_US).await;
+
+        let ratio = match self.modify_config(0, 1 << 5).await {
+            Ok(conf) => {
+                delay.delay_us(Config::from_bits(conf).filter_mode.conversion_time_us()).await;
+                self.read_rtd().await
+            }
+            Err(e) => Err(Error::Spi(e)),
+        };
+        bias(false);
+        let vbias_off = if internal_vbias {
+            self.modify_config(1 << 7, 0).await.map(|_| ())
+        } else {
+            Ok(())
+        };
+        let ratio = ratio?;
+        vbias_off?;
+
+        Ok(ratio)
+    }
+
+    /// Read the raw value, resistance and temperature of a single conversion, see
+    /// `crate::Max31865::read_all`.
+    pub async fn read_all(&mut self) -> Result<Reading, Error<E>> {
+        let raw = self.read_raw().await?;
+
+        Ok(self.params.reading(raw, self.channel))
+    }
+
+    /// Read a single conversion and stamp it with the given time, see
+    /// `crate::Max31865::read_all_at`.
+    pub async fn read_all_at(&mut self, now: u64) -> Result<Reading, Error<E>> {
+        Ok(Reading {
+            timestamp: Some(now),
+            ..self.read_all().await?
+        })
+    }
+
+    /// Read the temperature of a single conversion together with its fault status, see
+    /// `crate::Max31865::read_sample`.
+    pub async fn read_sample(&mut self) -> Result<crate::Sample, Error<E>> {
+        let raw = self.read_raw().await?;
+        let fault_bits = if raw & 1 != 0 { self.read(Register::FAULT_STATUS).await? } else { 0 };
+
+        Ok(self.params.to_sample(raw, fault_bits))
+    }
+
+    /// Read the resistance of the RTD element in Ohms.
+    pub async fn read_resistance(&mut self) -> Result<f32, Error<E>> {
+        let ratio = self.read_rtd().await?;
+
+        Ok(self.params.ratio_to_resistance(ratio))
+    }
+
+    /// Read the raw resistance value and convert it to degrees Celcius using the
+    /// Callendar-Van Dusen equation, see `crate::Max31865::read_temperature_c`.
+    #[cfg(feature = "float")]
+    pub async fn read_temperature_c(&mut self) -> Result<f32, Error<E>> {
+        let ratio = self.read_rtd().await?;
+
+        Ok(self.params.to_temperature_c(ratio_to_raw(ratio)))
+    }
+
+    /// Read the temperature in degrees Celcius multiplied by 100 as an `i16`, see
+    /// `crate::Max31865::read_temperature_centi_i16`.
+    pub async fn read_temperature_centi_i16(&mut self) -> Result<i16, Error<E>> {
+        let ratio = self.read_rtd().await?;
+        let reading = self.params.reading(ratio_to_raw(ratio), self.channel);
+
+        crate::celsius_to_i16(reading.temperature_c, 100.0).ok_or(Error::OutOfRange)
+    }
+
+    /// Read the temperature in degrees Celcius multiplied by 10 as an `i16`, see
+    /// `crate::Max31865::read_temperature_deci_i16`.
+    pub async fn read_temperature_deci_i16(&mut self) -> Result<i16, Error<E>> {
+        let ratio = self.read_rtd().await?;
+        let reading = self.params.reading(ratio_to_raw(ratio), self.channel);
+
+        crate::celsius_to_i16(reading.temperature_c, 10.0).ok_or(Error::OutOfRange)
+    }
+
+    /// Read the temperature in degrees Celcius as a fixed-point value, see
+    /// `crate::Max31865::read_temperature_fixed`. Requires the `fixed` feature.
+    #[cfg(all(feature = "lookup-table", feature = "fixed"))]
+    pub async fn read_temperature_fixed(&mut self) -> Result<fixed::types::I16F16, Error<E>> {
+        let temp = self.read_default_conversion().await?;
+
+        Ok(crate::centi_to_fixed(temp))
+    }
+
+    /// Read the temperature as a `uom` quantity, see
+    /// `crate::Max31865::read_thermodynamic_temperature`. Requires the `uom` feature.
+    #[cfg(feature = "uom")]
+    pub async fn read_thermodynamic_temperature(&mut self)
+        -> Result<uom::si::f32::ThermodynamicTemperature, Error<E>> {
+        let ratio = self.read_rtd().await?;
+        let reading = self.params.reading(ratio_to_raw(ratio), self.channel);
+
+        Ok(crate::celsius_to_uom(reading.temperature_c))
+    }
+
+    /// Read the raw RTD value, see `crate::Max31865::read_raw`.
+    pub async fn read_raw(&mut self) -> Result<u16, Error<E>> {
+        let buffer: [u8; 3] = self.read_many(Register::RTD_MSB).await?;
+        let raw = ((buffer[1] as u16) << 8) | buffer[2] as u16;
+        self.last_raw = Some(raw);
+        self.one_shot_pending = false;
+
+        Ok(raw)
+    }
+
+    /// The value returned by the last read of the RTD registers, see
+    /// `crate::Max31865::peek_raw`.
+    pub fn peek_raw(&self) -> Option<u16> {
+        self.last_raw
+    }
+
+    /// Read the RTD registers and decode the ratio and the fault bit, see
+    /// `crate::Max31865::read_rtd_reading`.
+    pub async fn read_rtd_reading(&mut self) -> Result<RtdReading, Error<E>> {
+        let raw = self.read_raw().await?;
+        Ok(RtdReading::from_raw(raw))
+    }
+
+    /// Read the RTD registers and the fault status register in one burst, see
+    /// `crate::Max31865::read_measurement_burst`.
+    pub async fn read_measurement_burst(&mut self) -> Result<(u16, u8), Error<E>> {
+        let buffer: [u8; 8] = self.read_many(Register::RTD_MSB).await?;
+        let raw = ((buffer[1] as u16) << 8) | buffer[2] as u16;
+        self.last_raw = Some(raw);
+        self.one_shot_pending = false;
+
+        Ok((raw, buffer[7]))
+    }
+
+    /// Read the 15 bit RTD ratio, checking the fault bit, see `crate::Max31865::read_rtd`.
+    pub async fn read_rtd(&mut self) -> Result<u16, Error<E>> {
+        let raw = self.read_raw().await?;
+        if raw & 1 != 0 {
+            return Err(Error::Fault(self.read_fault_status().await?));
+        }
+
+        Ok(raw_to_ratio(raw))
+    }
+
+    /// Set the high fault threshold ratio.
+    pub async fn set_high_fault_threshold(&mut self, ratio: u16) -> Result<(), Error<E>> {
+        self.write_threshold(Register::HIGH_FAULT_THRESHOLD_MSB, ratio).await?;
+        Ok(())
+    }
+
+    /// Set the low fault threshold ratio.
+    pub async fn set_low_fault_threshold(&mut self, ratio: u16) -> Result<(), Error<E>> {
+        self.write_threshold(Register::LOW_FAULT_THRESHOLD_MSB, ratio).await?;
+        Ok(())
+    }
+
+    /// Set the high fault threshold to a temperature in degrees Celcius.
+    pub async fn set_high_fault_threshold_temp(&mut self, celsius: f32) -> Result<(), Error<E>> {
+        let ratio = self.params.temp_to_ratio(celsius)
+            .ok_or(Error::OutOfRange)?;
+        self.set_high_fault_threshold(ratio).await
+    }
+
+    /// Set the low fault threshold to a temperature in degrees Celcius.
+    pub async fn set_low_fault_threshold_temp(&mut self, celsius: f32) -> Result<(), Error<E>> {
+        let ratio = self.params.temp_to_ratio(celsius)
+            .ok_or(Error::OutOfRange)?;
+        self.set_low_fault_threshold(ratio).await
+    }
+
+    /// Read the 15 bit high fault threshold value.
+    pub async fn read_high_fault_threshold(&mut self) -> Result<u16, Error<E>> {
+        Ok(self.read_threshold(Register::HIGH_FAULT_THRESHOLD_MSB).await?)
+    }
+
+    /// Read the 15 bit low fault threshold value.
+    pub async fn read_low_fault_threshold(&mut self) -> Result<u16, Error<E>> {
+        Ok(self.read_threshold(Register::LOW_FAULT_THRESHOLD_MSB).await?)
+    }
+
+    /// Program both fault thresholds from temperatures, see
+    /// `crate::Max31865::configure_alert`.
+    pub async fn configure_alert(&mut self, low_c: f32, high_c: f32) -> Result<(), Error<E>> {
+        let ratios = (self.params.temp_to_ratio(low_c), self.params.temp_to_ratio(high_c));
+        let (low, high) = match ratios {
+            (Some(low), Some(high)) if low < high => (low, high),
+            _ => return Err(Error::OutOfRange),
+        };
+
+        self.set_low_fault_threshold(low).await?;
+        self.set_high_fault_threshold(high).await
+    }
+
+    /// Program both fault thresholds from temperatures in degrees Celcius, see
+    /// `crate::Max31865::set_fault_thresholds_celsius`.
+    pub async fn set_fault_thresholds_celsius(&mut self, low_c: f32, high_c: f32)
+        -> Result<(), Error<E>>
+    {
+        self.configure_alert(low_c, high_c).await
+    }
+
+    /// Read which of the fault thresholds was crossed, see
+    /// `crate::Max31865::read_alert_state`.
+    pub async fn read_alert_state(&mut self) -> Result<AlertState, Error<E>> {
+        let faults = self.read_fault_status().await?;
+        Ok(AlertState {
+            high: faults.rtd_high_threshold,
+            low: faults.rtd_low_threshold,
+        })
+    }
+
+    /// Quickly check whether the RTD element appears to be connected, see
+    /// `crate::Max31865::is_sensor_connected`.
+    pub async fn is_sensor_connected(&mut self) -> Result<bool, Error<E>> {
+        let ratio = raw_to_ratio(self.read_raw().await?);
+        let faults = self.read_fault_status().await?;
+
+        Ok(sensor_connected(ratio, faults))
+    }
+
+    /// Verify that the device is connected, see `crate::Max31865::check_connection`.
+    pub async fn check_connection(&mut self) -> Result<(), Error<E>> {
+        let original = self.read_threshold(Register::HIGH_FAULT_THRESHOLD_MSB).await?;
+        let mut connected = true;
+        for &test in CONNECTION_PATTERNS.iter().chain(core::iter::once(&original)) {
+            self.write_threshold(Register::HIGH_FAULT_THRESHOLD_MSB, test).await?;
+            connected &= self.read_threshold(Register::HIGH_FAULT_THRESHOLD_MSB).await? == test;
+        }
+
+        if !connected {
+            return Err(Error::NoConnection);
+        }
+        Ok(())
+    }
+
+    /// Determine whether a working MAX31865 is on the bus, see
+    /// `crate::Max31865::detect`.
+    pub async fn detect(&mut self) -> Result<bool, Error<E>> {
+        match self.check_connection().await {
+            Ok(()) => Ok(true),
+            Err(Error::NoConnection) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Read and decode the fault status register.
+    pub async fn read_fault_status(&mut self) -> Result<FaultStatus, Error<E>> {
+        let bits = self.read(Register::FAULT_STATUS).await?;
+        Ok(FaultStatus::from_bits(bits))
+    }
+
+    /// Clear any latched faults in the fault status register.
+    pub async fn clear_faults(&mut self) -> Result<(), Error<E>> {
+        self.modify_config(0, 1 << 1).await?;
+        Ok(())
+    }
+
+    /// Run the automatic fault detection cycle and return the detected faults, see
+    /// `crate::Max31865::run_fault_detection`.
+    pub async fn run_fault_detection(&mut self, delay: &mut impl DelayNs) -> Result<FaultStatus, Error<E>> {
+        self.run_fault_detection_auto(delay).await
+    }
+
+    /// Run the fault detection cycle with the automatic delay of the device, see
+    /// `crate::Max31865::run_fault_detection_auto`.
+    pub async fn run_fault_detection_auto(&mut self, delay: &mut impl DelayNs)
+        -> Result<FaultStatus, Error<E>>
+    {
+        self.fault_detection_cycle(delay, None).await
+    }
+
+    /// Run the fault detection cycle with a delay controlled by the driver, see
+    /// `crate::Max31865::run_fault_detection_manual`.
+    pub async fn run_fault_detection_manual(&mut self, delay: &mut impl DelayNs, settle_us: u32)
+        -> Result<FaultStatus, Error<E>>
+    {
+        self.fault_detection_cycle(delay, Some(settle_us)).await
+    }
+
+    /// Disable V_BIAS and automatic conversion, see `crate::Max31865::power_down`.
+    pub async fn power_down(&mut self) -> Result<(), Error<E>> {
+        self.modify_config((1 << 7) | (1 << 6), 0).await?;
+        Ok(())
+    }
+
+    /// Enable V_BIAS again after a call to `power_down`.
+    pub async fn power_up(&mut self) -> Result<(), Error<E>> {
+        self.set_vbias(true).await
+    }
+
+    /// Enable or disable only V_BIAS, see `crate::Max31865::set_vbias`.
+    pub async fn set_vbias(&mut self, on: bool) -> Result<(), Error<E>> {
+        self.modify_config(1 << 7, (on as u8) << 7).await?;
+        Ok(())
+    }
+
+    /// Read a register directly, see `crate::Max31865::read_register`.
+    pub async fn read_register(&mut self, addr: u8) -> Result<u8, Error<E>> {
+        let mut buffer = [(addr & !W) | R, 0];
+        self.spi.transfer_in_place(&mut buffer).await?;
+        Ok(buffer[1])
+    }
+
+    /// Read all registers in a single transaction, see
+    /// `crate::Max31865::read_all_registers`.
+    pub async fn read_all_registers(&mut self) -> Result<[u8; 8], Error<E>> {
+        let buffer: [u8; 9] = self.read_many(Register::CONFIG).await?;
+        self.last_raw = Some(((buffer[2] as u16) << 8) | buffer[3] as u16);
+        self.one_shot_pending = false;
+
+        let mut registers = [0; 8];
+        registers.copy_from_slice(&buffer[1..]);
+        Ok(registers)
+    }
+
+    /// Write a register directly, see `crate::Max31865::write_register`.
+    pub async fn write_register(&mut self, addr: u8, val: u8) -> Result<(), Error<E>> {
+        self.spi.write(&[addr | W, val]).await?;
+        Ok(())
+    }
+
+    /// Read the configuration, the fault thresholds and the calibration of the device,
+    /// see `crate::Max31865::snapshot`.
+    pub async fn snapshot(&mut self) -> Result<DeviceState, Error<E>> {
+        Ok(DeviceState {
+            config: self.refresh_config().await?,
+            wiring: self.wiring,
+            high_fault_threshold: self.read_high_fault_threshold().await?,
+            low_fault_threshold: self.read_low_fault_threshold().await?,
+            rtd_type: self.params.rtd_type,
+            calibration: self.params.calibration,
+        })
+    }
+
+    /// Write back a state previously read with `snapshot`, see `crate::Max31865::restore`.
+    pub async fn restore(&mut self, state: &DeviceState) -> Result<(), Error<E>> {
+        self.stop_continuous().await?;
+        self.set_high_fault_threshold(state.high_fault_threshold).await?;
+        self.set_low_fault_threshold(state.low_fault_threshold).await?;
+        // a driver with a fixed RTD type keeps it, see `Max31865Pt100`
+        let rtd_type = if R0 == 0 { state.rtd_type } else { self.params.rtd_type };
+        self.params = ConversionParams::with_calibration(rtd_type, state.calibration)
+            .with_overrides_of(&self.params);
+        self.wiring = state.wiring;
+        self.configure_with(Config { one_shot: false, ..state.config }).await
+    }
+
+    async fn fault_detection_cycle(&mut self, delay: &mut impl DelayNs, settle_us: Option<u32>)
+        -> Result<FaultStatus, Error<E>>
+    {
+        let auto = self.config & (1 << 6);
+        self.clear_faults().await?;
+        if self.config & (1 << 7) == 0 {
+            self.modify_config(0, 1 << 7).await?;
+            delay.delay_us(VBIAS_SETTLE_TIME_US).await;
+        }
+
+        match settle_us {
+            None => {
+                self.modify_config(1 << 6, 0b0100).await?;
+            }
+            Some(settle_us) => {
+                self.modify_config(1 << 6, 0b1000).await?;
+                delay.delay_us(settle_us).await;
+                self.modify_config(0, 0b1100).await?;
+                delay.delay_us(settle_us).await;
+            }
+        }
+
+        let mut finished = false;
+        for _ in 0..FAULT_DETECTION_POLLS {
+            delay.delay_us(FAULT_DETECTION_POLL_US).await;
+            if self.read(Register::CONFIG).await? & 0b1100 == 0 {
+                finished = true;
+                break;
+            }
+        }
+        self.modify_config(0, auto).await?;
+
+        if !finished {
+            return Err(Error::Timeout);
+        }
+        self.read_fault_status().await
+    }
+
+    async fn modify_config(&mut self, clear: u8, set: u8) -> Result<u8, E> {
+        let conf = (self.config & !clear) | set;
+        self.write(Register::CONFIG, conf).await?;
+        self.cache_config(conf);
+        Ok(conf)
+    }
+
+    async fn read_threshold(&mut self, msb: Register) -> Result<u16, E> {
+        let buffer: [u8; 3] = self.read_many(msb).await?;
+        Ok(raw_to_ratio(((buffer[1] as u16) << 8) | buffer[2] as u16))
+    }
+
+    async fn write_threshold(&mut self, msb: Register, ratio: u16) -> Result<(), E> {
+        let val = ratio_to_raw(ratio);
+        self.spi.write(&[msb.write_address(), (val >> 8) as u8, val as u8]).await
+    }
+
+    fn cache_config(&mut self, conf: u8) {
+        // the one shot bit isn't cached, but remembered until the RTD is read
+        self.one_shot_pending |= conf & (1 << 5) != 0;
+        self.config = conf & CONFIG_SETTINGS;
+        self.wiring = wiring_for_config(self.wiring, self.config);
+    }
+
+    // diagnose why the ready pin wasn't set low in time, see
+    // `crate::Max31865::wait_for_ready`
+    async fn ready_timeout(&mut self) -> Error<E> {
+        let bits = match self.read(Register::CONFIG).await {
+            Ok(bits) => bits,
+            Err(e) => return Error::Spi(e),
+        };
+        let config = Config::from_bits(bits);
+        if self.one_shot_pending || config.one_shot {
+            Error::Timeout
+        } else if !config.vbias || !config.conversion_mode {
+            Error::NotConverting(config)
+        } else {
+            Error::Timeout
+        }
+    }
+
+    async fn read(&mut self, reg: Register) -> Result<u8, E> {
+        let buffer: [u8; 2] = self.read_many(reg).await?;
+        Ok(buffer[1])
+    }
+
+    async fn read_many<const N: usize>(&mut self, reg: Register) -> Result<[u8; N], E> {
+        let mut buffer = [0u8; N];
+        buffer[0] = reg.read_address();
+        self.spi.transfer_in_place(&mut buffer).await?;
+
+        Ok(buffer)
+    }
+
+    async fn write(&mut self, reg: Register, val: u8) -> Result<(), E> {
+        self.spi.write(&[reg.write_address(), val]).await
+    }
+}
+
+impl<E, SPI, RDY, const R0: u32> Max31865<SPI, RDY, R0>
+where
+    SPI: SpiDevice<u8, Error = E>,
+    RDY: Wait
+{
+    /// Wait for the ready pin to signal that a new conversion is available.
+    ///
+    /// # Remarks
+    ///
+    /// Returns `Error::ReadyPin` if the pin can't be waited on.
+    pub async fn wait_ready(&mut self) -> Result<(), Error<E>> {
+        self.rdy.wait_for_low().await.map_err(|_| Error::ReadyPin)
+    }
+
+    /// Wait for the ready pin, giving up after `timeout_us` microseconds, see
+    /// `crate::Max31865::wait_for_ready_timeout`.
+    ///
+    /// # Remarks
+    ///
+    /// Unlike `wait_ready` this doesn't hang if the ready pin is never set low, e.g.
+    /// because it isn't connected. The pin is always checked at least once, even for a
+    /// timeout of 0. If no conversion became available in time the configuration
+    /// register is read back and the error is diagnosed like for the blocking driver,
+    /// i.e. `Error::NotConverting` if V_BIAS or automatic conversion is disabled and no
+    /// one shot conversion is pending, and `Error::Timeout` otherwise. Returns
+    /// `Error::ReadyPin` if the pin can't be waited on.
+    pub async fn wait_ready_timeout(&mut self, delay: &mut impl DelayNs, timeout_us: u32)
+        -> Result<(), Error<E>>
+    {
+        let ready = {
+            let mut wait = pin!(self.rdy.wait_for_low());
+            let mut timeout = pin!(delay.delay_us(timeout_us));
+            poll_fn(|cx| {
+                if let Poll::Ready(result) = wait.as_mut().poll(cx) {
+                    return Poll::Ready(Some(result));
+                }
+                timeout.as_mut().poll(cx).map(|_| None)
+            }).await
+        };
+
+        match ready {
+            Some(result) => result.map_err(|_| Error::ReadyPin),
+            None => Err(self.ready_timeout().await),
+        }
+    }
+
+    /// Wait for the next conversion and read the converted temperature.
+    ///
+    /// # Remarks
+    ///
+    /// If automatic conversion is disabled a one shot conversion is started first, so
+    /// V_BIAS has to be enabled and settled already, see `set_vbias`. With automatic
+    /// conversion this waits for the next conversion of the running cycle.
+    #[cfg(feature = "lookup-table")]
+    pub async fn read_temperature(&mut self) -> Result<i32, Error<E>> {
+        if self.config & (1 << 6) == 0 {
+            self.modify_config(0, 1 << 5).await?;
+        }
+        self.wait_ready().await?;
+        self.read_default_conversion().await
+    }
+
+    /// Treat the device as a source of readings in automatic conversion mode, see
+    /// `crate::Max31865::readings`.
+    pub fn readings(&mut self) -> Readings<'_, SPI, RDY, R0> {
+        Readings {
+            max31865: self,
+            timeout_us: READINGS_POLLS * READINGS_POLL_US,
+        }
+    }
+}
+
+/// A stream of readings in automatic conversion mode, see `Max31865::readings`.
+///
+/// Unlike `crate::Readings` this isn't turned into an iterator, as there is no stream
+/// trait in `core`. Loop over `next_reading` instead, each call reads the RTD
+/// registers and so returns the ready pin to high for the next conversion:
+///
+/// ```ignore
+/// let mut readings = max31865.readings();
+/// loop {
+///     let reading = readings.next_reading(&mut delay).await?;
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Readings<'a, SPI, RDY, const R0: u32 = 0> {
+    max31865: &'a mut Max31865<SPI, RDY, R0>,
+    timeout_us: u32,
+}
+
+impl<'a, E, SPI, RDY, const R0: u32> Readings<'a, SPI, RDY, R0>
+where
+    SPI: SpiDevice<u8, Error = E>,
+    RDY: Wait
+{
+    /// Set the time in microseconds `next_reading` waits for the ready pin before it
+    /// gives up. The default of 100ms covers the first conversion after automatic
+    /// conversion was enabled.
+    pub fn timeout_us(mut self, timeout_us: u32) -> Self {
+        self.timeout_us = timeout_us;
+        self
+    }
+
+    /// Wait for the next conversion and return it, see
+    /// `crate::Readings::next_reading`.
+    ///
+    /// # Remarks
+    ///
+    /// The ready pin is awaited with `Max31865::wait_ready_timeout`, so the errors are
+    /// diagnosed the same way. Returns `Error::Fault` if the fault bit of the
+    /// conversion is set.
+    pub async fn next_reading(&mut self, delay: &mut impl DelayNs) -> Result<Reading, Error<E>> {
+        self.max31865.wait_ready_timeout(delay, self.timeout_us).await?;
+        let raw = self.max31865.read_raw().await?;
+        if raw & 1 != 0 {
+            return Err(Error::Fault(self.max31865.read_fault_status().await?));
+        }
+
+        Ok(self.max31865.params.reading(raw, self.max31865.channel))
+    }
+}
+
+impl<E, SPI, RDY, const R0: u32> Max31865<SPI, RDY, R0>
+where
+    SPI: SpiDevice<u8, Error = E>,
+    RDY: InputPin
+{
+    /// Read the converted temperature if a conversion is available, see
+    /// `crate::Max31865::read_if_ready`.
+    ///
+    /// # Remarks
+    ///
+    /// The ready pin is only sampled, not awaited, so this returns `None` right away
+    /// while a conversion is in progress. Returns `Error::ReadyPin` if the pin can't be
+    /// read.
+    #[cfg(feature = "lookup-table")]
+    pub async fn read_if_ready(&mut self) -> Result<Option<i32>, Error<E>> {
+        if self.rdy.is_high().map_err(|_| Error::ReadyPin)? {
+            return Ok(None);
+        }
+
+        self.read_default_conversion().await.map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::tests::{read, write};
+    use core::convert::Infallible;
+    use core::task::{Context, Waker};
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+    use embedded_hal_mock::eh1::MockError;
+    use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction};
+    use std::io::ErrorKind;
+    use std::vec;
+    use std::vec::Vec;
+
+    // the mocks never return `Pending`, so polling once completes the future
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("future didn't complete"),
+        }
+    }
+
+    // a device expecting exactly `transactions`, and a handle to check them with
+    // `done`
+    fn device(transactions: &[Vec<Transaction<u8>>]) -> (Max31865<SpiMock<u8>>, SpiMock<u8>) {
+        let spi = SpiMock::new(transactions.iter().flatten());
+        (Max31865::new_without_ready(spi.clone()).unwrap(), spi)
+    }
+
+    #[test]
+    fn run_fault_detection_cycle() {
+        let (mut max31865, mut spi) = device(&[
+            write(&[0x80, 0b1101_0001]),
+            write(&[0x80, 0b1101_0011]),
+            write(&[0x80, 0b1001_0101]),
+            read(0x00, &[0b1001_0001]),
+            write(&[0x80, 0b1101_0001]),
+            read(0x07, &[0]),
+        ]);
+        block_on(max31865.configure_with(Config::default())).unwrap();
+        let status = block_on(max31865.run_fault_detection(&mut NoopDelay::new())).unwrap();
+        assert!(status.is_ok());
+        spi.done();
+    }
+
+    #[test]
+    fn set_filter_pauses_conversion() {
+        let (mut max31865, mut spi) = device(&[
+            write(&[0x80, 0b1101_0001]),
+            write(&[0x80, 0b1001_0001]),
+            write(&[0x80, 0b1001_0000]),
+            write(&[0x80, 0b1101_0000]),
+        ]);
+        block_on(max31865.configure_with(Config::default())).unwrap();
+        block_on(max31865.set_filter(FilterMode::Filter60Hz)).unwrap();
+        assert_eq!(max31865.config(), Config::default().filter(FilterMode::Filter60Hz));
+        spi.done();
+    }
+
+    #[test]
+    fn calibrate_at_100c() {
+        // 138.51 Ohms with a 430 Ohm reference is a ratio of 10555
+        let (mut max31865, mut spi) = device(&[read(0x01, &ratio_to_raw(10555).to_be_bytes())]);
+        assert_eq!(block_on(max31865.calibrate_at_100c()).unwrap(), 43000);
+        assert_eq!(max31865.calibration(), 43000);
+        spi.done();
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn read_averaged_discards_faults() {
+        // 0 degrees Celcius, 100 degrees Celcius and a faulted sample
+        let samples = [ratio_to_raw(8192), ratio_to_raw(11347), ratio_to_raw(8192) | 1];
+        let (mut max31865, mut spi) = device(&[
+            write(&[0x80, 0b1000_0000]),
+            write(&[0x80, 0b1010_0000]),
+            read(0x01, &samples[0].to_be_bytes()),
+            write(&[0x80, 0b1010_0000]),
+            read(0x01, &samples[1].to_be_bytes()),
+            write(&[0x80, 0b1010_0000]),
+            read(0x01, &samples[2].to_be_bytes()),
+            read(0x07, &[0b1000_0000]),
+            write(&[0x80, 0b1000_0010]),
+        ]);
+        block_on(max31865.configure_with(Config::empty().vbias(true))).unwrap();
+        let temp = block_on(max31865.read_averaged(3, &mut NoopDelay::new())).unwrap();
+        assert!((temp - 5000).abs() <= 10, "{} != 5000", temp);
+        spi.done();
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn read_one_shot_averages_sample_count() {
+        let samples = [ratio_to_raw(8192), ratio_to_raw(11347)];
+        let (mut max31865, mut spi) = device(&[
+            write(&[0x80, 0b1000_0000]),
+            write(&[0x80, 0b1010_0000]),
+            read(0x01, &samples[0].to_be_bytes()),
+            write(&[0x80, 0b1010_0000]),
+            read(0x01, &samples[1].to_be_bytes()),
+        ]);
+        block_on(max31865.configure_with(Config::empty().vbias(true))).unwrap();
+        max31865.set_sample_count(2);
+        let temp = block_on(max31865.read_one_shot(&mut NoopDelay::new())).unwrap();
+        assert!((temp - 5000).abs() <= 10, "{} != 5000", temp);
+        spi.done();
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn read_averaged_all_faulted() {
+        let (mut max31865, mut spi) = device(&[
+            write(&[0x80, 0b1000_0000]),
+            write(&[0x80, 0b1010_0000]),
+            read(0x01, &[0x00, 0x01]),
+            read(0x07, &[0b0100_0000]),
+            write(&[0x80, 0b1000_0010]),
+        ]);
+        block_on(max31865.configure_with(Config::empty().vbias(true))).unwrap();
+        let result = block_on(max31865.read_averaged(1, &mut NoopDelay::new()));
+        assert!(matches!(result, Err(Error::Fault(status)) if status.rtd_low_threshold));
+        assert!(matches!(block_on(max31865.read_averaged(0, &mut NoopDelay::new())),
+            Err(Error::OutOfRange)));
+        spi.done();
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn read_temperature_starts_one_shot() {
+        let raw = ratio_to_raw(8192);
+        let mut spi = SpiMock::new(&[
+            write(&[0x80, 0b1000_0000]),
+            write(&[0x80, 0b1010_0000]),
+            read(0x01, &raw.to_be_bytes()),
+        ].concat());
+        let mut rdy = PinMock::new(&[PinTransaction::wait_for_state(PinState::Low)]);
+        let mut max31865 = Max31865::new(spi.clone(), rdy.clone()).unwrap();
+        block_on(max31865.configure_with(Config::empty().vbias(true))).unwrap();
+        assert_eq!(block_on(max31865.read_temperature()).unwrap(), 0);
+        spi.done();
+        rdy.done();
+    }
+
+    #[test]
+    fn wait_ready_returns_pin_error() {
+        let mut spi = SpiMock::new(&[]);
+        let mut rdy = PinMock::new(&[PinTransaction::wait_for_state(PinState::Low)
+            .with_error(MockError::Io(ErrorKind::NotConnected))]);
+        let mut max31865 = Max31865::new(spi.clone(), rdy.clone()).unwrap();
+        assert!(matches!(block_on(max31865.wait_ready()), Err(Error::ReadyPin)));
+        spi.done();
+        rdy.done();
+    }
+
+    #[test]
+    fn new_with_mode() {
+        let mut spi = SpiMock::new(&[]);
+        let max31865 = Max31865::new_with_mode(spi.clone(), NoReady, MODE_1).unwrap();
+        assert_eq!(max31865.mode(), MODE_1);
+        let mode_0 = Mode {
+            phase: hal::spi::Phase::CaptureOnFirstTransition,
+            polarity: hal::spi::Polarity::IdleLow,
+        };
+        assert!(matches!(Max31865::new_with_mode(spi.clone(), NoReady, mode_0),
+            Err(Error::UnsupportedMode)));
+        spi.done();
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    fn read_temperature_c_applies_offset() {
+        let raw = ratio_to_raw(11347);
+        let (mut max31865, mut spi) = device(&[read(0x01, &raw.to_be_bytes())]);
+        max31865.set_temperature_offset(-50);
+        let result = block_on(max31865.read_temperature_c()).unwrap();
+        assert!((result - 99.5).abs() <= 0.1, "{} != 99.5", result);
+        spi.done();
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn read_temperature_f_and_k() {
+        let raw = ratio_to_raw(11347);
+        let (mut max31865, mut spi) = device(&[
+            read(0x01, &raw.to_be_bytes()),
+            read(0x01, &raw.to_be_bytes()),
+        ]);
+        let f = block_on(max31865.read_temperature_f()).unwrap();
+        assert!((f - 212.0).abs() <= 0.2, "{} != 212", f);
+        let k = block_on(max31865.read_temperature_k()).unwrap();
+        assert!((k - 373.15).abs() <= 0.1, "{} != 373.15", k);
+        spi.done();
+    }
+
+    #[test]
+    fn reference_resistance() {
+        let mut spi = SpiMock::new(&[]);
+        let mut max31865 = Max31865::new_without_ready(spi.clone()).unwrap();
+        assert_eq!(max31865.reference_resistance(), 400.0);
+        max31865.set_reference_resistance(ReferenceResistance::from_ohms(430.2));
+        assert_eq!(max31865.reference_resistance(), 430.2);
+        spi.done();
+    }
+
+    #[test]
+    fn rtd_type_constructors() {
+        let mut spi = SpiMock::new(&[]);
+        let max31865: Max31865Pt100<_> = Max31865::new_pt100(spi.clone(), NoReady).unwrap();
+        assert_eq!(max31865.reference_resistance(), 400.0);
+
+        let max31865: Max31865Pt1000<_> = Max31865::new_pt1000(spi.clone(), NoReady).unwrap();
+        assert_eq!(max31865.reference_resistance(), 4300.0);
+        spi.done();
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn read_default_conversion_f() {
+        let raw = ratio_to_raw(11347);
+        let (mut max31865, mut spi) = device(&[read(0x01, &raw.to_be_bytes())]);
+        let f = block_on(max31865.read_default_conversion_f()).unwrap();
+        assert!((f - 21200).abs() <= 20, "{} != 21200", f);
+        spi.done();
+    }
+
+    // a ready pin that is never set low
+    struct StuckPin;
+
+    impl hal::digital::ErrorType for StuckPin {
+        type Error = Infallible;
+    }
+
+    impl Wait for StuckPin {
+        async fn wait_for_high(&mut self) -> Result<(), Infallible> {
+            core::future::pending().await
+        }
+
+        async fn wait_for_low(&mut self) -> Result<(), Infallible> {
+            core::future::pending().await
+        }
+
+        async fn wait_for_rising_edge(&mut self) -> Result<(), Infallible> {
+            core::future::pending().await
+        }
+
+        async fn wait_for_falling_edge(&mut self) -> Result<(), Infallible> {
+            core::future::pending().await
+        }
+
+        async fn wait_for_any_edge(&mut self) -> Result<(), Infallible> {
+            core::future::pending().await
+        }
+    }
+
+    #[test]
+    fn wait_ready_timeout() {
+        let mut spi = SpiMock::new(&[
+            write(&[0x80, 0b1100_0000]),
+            read(0x00, &[0b1100_0000]),
+        ].concat());
+        let mut max31865 = Max31865::new(spi.clone(), StuckPin).unwrap();
+        block_on(max31865.configure_with(Config::empty().vbias(true).conversion_mode(true)))
+            .unwrap();
+        let result = block_on(max31865.wait_ready_timeout(&mut NoopDelay::new(), 100_000));
+        assert!(matches!(result, Err(Error::Timeout)));
+        spi.done();
+    }
+
+    #[test]
+    fn wait_ready_timeout_not_converting() {
+        let mut spi = SpiMock::new(&[read(0x00, &[0b1000_0000])].concat());
+        let mut max31865 = Max31865::new(spi.clone(), StuckPin).unwrap();
+        match block_on(max31865.wait_ready_timeout(&mut NoopDelay::new(), 1_000)) {
+            Err(Error::NotConverting(config)) => assert!(!config.conversion_mode),
+            e => panic!("{:?}", e),
+        }
+        // the read back configuration isn't cached
+        assert!(!max31865.config().vbias);
+        spi.done();
+    }
+
+    #[test]
+    fn wait_ready_timeout_after_one_shot() {
+        let mut spi = SpiMock::new(&[
+            write(&[0x80, 0b1010_0000]),
+            read(0x00, &[0b1000_0000]),
+        ].concat());
+        let mut max31865 = Max31865::new(spi.clone(), StuckPin).unwrap();
+        block_on(max31865.configure_with(Config::empty().vbias(true).one_shot(true))).unwrap();
+        let result = block_on(max31865.wait_ready_timeout(&mut NoopDelay::new(), 1_000));
+        assert!(matches!(result, Err(Error::Timeout)));
+        spi.done();
+    }
+
+    #[test]
+    fn wait_ready_timeout_samples_pin_once() {
+        let mut spi = SpiMock::new(&[]);
+        let mut rdy = PinMock::new(&[PinTransaction::wait_for_state(PinState::Low)]);
+        let mut max31865 = Max31865::new(spi.clone(), rdy.clone()).unwrap();
+        block_on(max31865.wait_ready_timeout(&mut NoopDelay::new(), 0)).unwrap();
+        spi.done();
+        rdy.done();
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn read_if_ready() {
+        let raw = ratio_to_raw(8192);
+        let mut spi = SpiMock::new(&read(0x01, &raw.to_be_bytes()));
+        let mut rdy = PinMock::new(&[
+            PinTransaction::get(PinState::High),
+            PinTransaction::get(PinState::Low),
+        ]);
+        let mut max31865 = Max31865::new(spi.clone(), rdy.clone()).unwrap();
+        assert_eq!(block_on(max31865.read_if_ready()).unwrap(), None);
+        assert_eq!(block_on(max31865.read_if_ready()).unwrap(), Some(0));
+        spi.done();
+        rdy.done();
+    }
+
+    #[test]
+    fn next_reading() {
+        let raw = ratio_to_raw(11347);
+        let mut spi = SpiMock::new(&[
+            read(0x01, &raw.to_be_bytes()),
+            read(0x01, &(raw | 1).to_be_bytes()),
+            read(0x07, &[0b1000_0000]),
+        ].concat());
+        let mut rdy = PinMock::new(&[
+            PinTransaction::wait_for_state(PinState::Low),
+            PinTransaction::wait_for_state(PinState::Low),
+        ]);
+        let mut max31865 = Max31865::new(spi.clone(), rdy.clone()).unwrap();
+        let mut delay = NoopDelay::new();
+        let mut readings = max31865.readings();
+        let reading = block_on(readings.next_reading(&mut delay)).unwrap();
+        assert_eq!(reading.raw, raw);
+        assert!((reading.temperature_c - 100.0).abs() <= 0.1);
+        match block_on(readings.next_reading(&mut delay)) {
+            Err(Error::Fault(status)) => assert!(status.rtd_high_threshold),
+            e => panic!("{:?}", e),
+        }
+        spi.done();
+        rdy.done();
+    }
+
+    #[test]
+    fn readings_stream() {
+        let raws = [ratio_to_raw(8192), ratio_to_raw(8200), ratio_to_raw(8208)];
+        let transactions: Vec<_> = raws.iter().map(|raw| read(0x01, &raw.to_be_bytes()))
+            .collect();
+        let mut spi = SpiMock::new(&transactions.concat());
+        let mut rdy = PinMock::new(&vec![PinTransaction::wait_for_state(PinState::Low); 3]);
+        let mut max31865 = Max31865::new(spi.clone(), rdy.clone()).unwrap();
+        let mut delay = NoopDelay::new();
+        let mut readings = max31865.readings().timeout_us(1_000);
+        for raw in raws {
+            assert_eq!(block_on(readings.next_reading(&mut delay)).unwrap().raw, raw);
+        }
+        spi.done();
+        rdy.done();
+    }
+}
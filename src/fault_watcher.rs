@@ -0,0 +1,147 @@
+//! Software temperature limits with hysteresis
+//!
+//! The fault thresholds of the MAX31865 are single-valued and latch, so a reading
+//! hovering at a limit makes them chatter. `FaultWatcher` instead tracks high and low
+//! temperature limits in software and only reports a change after the temperature
+//! crossed the hysteresis band, e.g. for thermostat-style control.
+
+use crate::Reading;
+
+/// One of the temperature limits of a `FaultWatcher`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Limit {
+    High,
+    Low,
+}
+
+/// A change of the limit state reported by `FaultWatcher::update`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FaultEvent {
+    /// The temperature crossed the limit.
+    Entered(Limit),
+    /// The temperature returned inside the limit by more than the hysteresis.
+    Cleared(Limit),
+}
+
+/// Tracks high and low temperature limits with hysteresis.
+#[derive(Debug, Clone, Copy)]
+pub struct FaultWatcher {
+    low: f32,
+    high: f32,
+    hysteresis: f32,
+    active: Option<Limit>,
+}
+
+impl FaultWatcher {
+    /// Create a new watcher.
+    ///
+    /// # Arguments
+    ///
+    /// * `low` - The low temperature limit in degrees Celcius.
+    /// * `high` - The high temperature limit in degrees Celcius.
+    /// * `hysteresis` - The distance in degrees Celcius the temperature has to return
+    ///                  inside a limit before it is cleared.
+    pub fn new(low: f32, high: f32, hysteresis: f32) -> FaultWatcher {
+        FaultWatcher {
+            low,
+            high,
+            hysteresis,
+            active: None,
+        }
+    }
+
+    /// The limit that is currently exceeded, if any.
+    pub fn active(&self) -> Option<Limit> {
+        self.active
+    }
+
+    /// Feed the next reading and return the resulting change, if any.
+    ///
+    /// # Remarks
+    ///
+    /// A limit is entered as soon as the temperature is above the high or below the low
+    /// limit, and cleared once it is back inside by more than the hysteresis. If the
+    /// temperature jumps from one limit straight past the other, only the newly entered
+    /// limit is reported, which implies the previous one was cleared. Readings with the
+    /// fault bit set are ignored.
+    pub fn update(&mut self, reading: &Reading) -> Option<FaultEvent> {
+        if reading.fault {
+            return None;
+        }
+
+        let temp = reading.temperature_c;
+        let next = if temp > self.high {
+            Some(Limit::High)
+        } else if temp < self.low {
+            Some(Limit::Low)
+        } else {
+            match self.active {
+                Some(Limit::High) if temp >= self.high - self.hysteresis => Some(Limit::High),
+                Some(Limit::Low) if temp <= self.low + self.hysteresis => Some(Limit::Low),
+                _ => None,
+            }
+        };
+
+        let event = match (self.active, next) {
+            (previous, Some(limit)) if previous != Some(limit) => Some(FaultEvent::Entered(limit)),
+            (Some(limit), None) => Some(FaultEvent::Cleared(limit)),
+            _ => None,
+        };
+        self.active = next;
+        event
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reading(temperature_c: f32) -> Reading {
+        Reading {
+            raw: 0,
+            resistance_ohms: 0.0,
+            temperature_c,
+            fault: false,
+            channel: 0,
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn reports_rising_edge_once() {
+        let mut watcher = FaultWatcher::new(0.0, 100.0, 5.0);
+        assert_eq!(watcher.update(&reading(50.0)), None);
+        assert_eq!(watcher.update(&reading(101.0)), Some(FaultEvent::Entered(Limit::High)));
+        // no repeated events while the limit is exceeded
+        assert_eq!(watcher.update(&reading(110.0)), None);
+        assert_eq!(watcher.update(&reading(101.0)), None);
+        assert_eq!(watcher.active(), Some(Limit::High));
+    }
+
+    #[test]
+    fn clears_after_hysteresis() {
+        let mut watcher = FaultWatcher::new(0.0, 100.0, 5.0);
+        watcher.update(&reading(101.0));
+        // inside the hysteresis band the limit stays active
+        assert_eq!(watcher.update(&reading(99.0)), None);
+        assert_eq!(watcher.update(&reading(95.5)), None);
+        assert_eq!(watcher.update(&reading(94.0)), Some(FaultEvent::Cleared(Limit::High)));
+        assert_eq!(watcher.active(), None);
+
+        assert_eq!(watcher.update(&reading(-1.0)), Some(FaultEvent::Entered(Limit::Low)));
+        assert_eq!(watcher.update(&reading(4.0)), None);
+        assert_eq!(watcher.update(&reading(6.0)), Some(FaultEvent::Cleared(Limit::Low)));
+    }
+
+    #[test]
+    fn jumps_between_limits_and_ignores_faults() {
+        let mut watcher = FaultWatcher::new(0.0, 100.0, 5.0);
+        watcher.update(&reading(101.0));
+        assert_eq!(watcher.update(&reading(-1.0)), Some(FaultEvent::Entered(Limit::Low)));
+        let faulted = Reading { fault: true, ..reading(50.0) };
+        assert_eq!(watcher.update(&faulted), None);
+        assert_eq!(watcher.active(), Some(Limit::Low));
+    }
+}
@@ -3,197 +3,3393 @@
 //! # References
 //! - Datasheet: https://datasheets.maximintegrated.com/en/ds/MAX31865.pdf
 
-#![feature(unsize)]
 #![no_std]
+#![allow(clippy::doc_overindented_list_items)]
 
 extern crate embedded_hal as hal;
 
-use hal::digital::{InputPin, OutputPin};
-use hal::spi::{Mode, Phase, Polarity};
-use hal::blocking::spi;
-
-use core::marker::Unsize;
-use core::mem;
+use hal::delay::DelayNs;
+use hal::digital::InputPin;
+use hal::spi::{Mode, Phase, Polarity, SpiDevice};
 
 #[cfg(feature = "doc")]
 pub use examples;
 
+/// The SPI mode used by the MAX31865 (CPOL = 1, CPHA = 1, i.e. mode 3).
+/// 
+/// The device also supports mode 1, but not modes 0 and 2, which produce shifted,
+/// intermittently wrong values. If the bus is configured separately from the driver,
+/// make sure it uses this mode and use `Max31865::check_connection` to verify it.
 pub const MODE : Mode = Mode {
     phase: Phase::CaptureOnSecondTransition,
     polarity: Polarity::IdleHigh    
 };
 
+/// SPI mode 1 (CPOL = 0, CPHA = 1), which the MAX31865 supports as well.
+/// 
+/// The device detects the clock polarity from the idle level of SCLK when CS goes
+/// low, so either this or `MODE_3` works.
+pub const MODE_1: Mode = Mode {
+    phase: Phase::CaptureOnSecondTransition,
+    polarity: Polarity::IdleLow,
+};
+
+/// SPI mode 3 (CPOL = 1, CPHA = 1), the same as `MODE`.
+pub const MODE_3: Mode = MODE;
+
+/// The recommended SPI mode for the MAX31865, see `MODE`.
+pub fn recommended_mode() -> Mode {
+    MODE
+}
+
+#[cfg(not(any(feature = "lookup-table", feature = "float")))]
+compile_error!("at least one of the `lookup-table` and `float` features must be enabled");
+
 pub mod temp_conversion;
+pub mod fault_watcher;
+pub mod plausibility;
+#[cfg(feature = "async")]
+pub mod asynch;
 
+/// The time V_BIAS needs to settle after being enabled before a conversion is valid.
+/// 
+/// The datasheet requires 10.5 time constants of the input filter plus 1ms, which
+/// is covered by 10ms for the recommended 10nF input filter capacitor.
+pub const VBIAS_SETTLE_TIME_US: u32 = 10_000;
+
+/// Errors reported by the driver.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error<E> {
+    /// An error on the SPI bus.
+    Spi(E),
+    /// The device reported a fault, see the fault status for details.
+    Fault(FaultStatus),
+    /// The conversion did not complete in time.
+    Timeout,
+    /// A value is outside of the supported range.
+    OutOfRange,
+    /// The filter can't be changed while automatic conversion is enabled.
+    FilterChange,
+    /// The device didn't read back the written value, see `Max31865::check_connection`.
+    NoConnection,
+    /// No conversion became available because V_BIAS or automatic conversion is
+    /// disabled in the configuration read back from the device, see
+    /// `Max31865::wait_for_ready`.
+    NotConverting(Config),
+    /// The ready pin couldn't be read.
+    ReadyPin,
+    /// The SPI mode isn't supported by the MAX31865, see `Max31865::new_with_mode`.
+    UnsupportedMode,
+}
+
+impl<E> From<E> for Error<E> {
+    fn from(e: E) -> Self {
+        Error::Spi(e)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FilterMode {
     Filter60Hz = 0,
     Filter50Hz = 1
 }
 
+impl FilterMode {
+    /// Decode the filter bit (D0) of the configuration register.
+    pub fn from_bit(bit: bool) -> FilterMode {
+        if bit { FilterMode::Filter50Hz } else { FilterMode::Filter60Hz }
+    }
+
+    /// The value of the filter bit (D0) of the configuration register, `true` for 50Hz.
+    pub fn to_bit(&self) -> bool {
+        matches!(self, FilterMode::Filter50Hz)
+    }
+
+    /// The time a one shot conversion takes with this filter in milliseconds.
+    /// 
+    /// This is 52ms for 60Hz and 62.5ms for 50Hz, rounded up so that waiting this long
+    /// is always sufficient. If V_BIAS was disabled, `VBIAS_SETTLE_TIME_US` has to be
+    /// waited in addition before the conversion is started.
+    pub fn conversion_time_ms(&self) -> u32 {
+        self.conversion_time_us().div_ceil(1000)
+    }
+
+    /// The time a one shot conversion takes with this filter in microseconds.
+    fn conversion_time_us(&self) -> u32 {
+        match self {
+            FilterMode::Filter60Hz => 52_000,
+            FilterMode::Filter50Hz => 62_500,
+        }
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for FilterMode {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        f.write_str(match self {
+            FilterMode::Filter60Hz => "60Hz",
+            FilterMode::Filter50Hz => "50Hz",
+        })
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for FilterMode {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        f.write_str(match self {
+            FilterMode::Filter60Hz => "Filter60Hz",
+            FilterMode::Filter50Hz => "Filter50Hz",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SensorType {
     TwoOrFourWire = 0,
     ThreeWire = 1,
 }
 
-pub struct Max31865<SPI, NCS, RDY> {
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for SensorType {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        f.write_str(match self {
+            SensorType::TwoOrFourWire => "2/4-wire",
+            SensorType::ThreeWire => "3-wire",
+        })
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for SensorType {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        f.write_str(match self {
+            SensorType::TwoOrFourWire => "TwoOrFourWire",
+            SensorType::ThreeWire => "ThreeWire",
+        })
+    }
+}
+
+/// The physical wiring of the RTD element.
+/// 
+/// Two and four wire sensors use the same configuration (`SensorType::TwoOrFourWire`),
+/// so the registers write identically and `FourWire` is only recorded by the driver for
+/// reporting. A four wire connection cancels the lead resistance and is therefore more
+/// accurate than a two wire connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Wiring {
+    TwoWire,
+    ThreeWire,
+    FourWire,
+}
+
+impl Wiring {
+    /// The sensor type to configure for this wiring.
+    pub fn sensor_type(&self) -> SensorType {
+        match self {
+            Wiring::TwoWire | Wiring::FourWire => SensorType::TwoOrFourWire,
+            Wiring::ThreeWire => SensorType::ThreeWire,
+        }
+    }
+}
+
+// the recorded wiring if it matches the sensor type bit of the configuration,
+// otherwise the best guess for the bit
+fn wiring_for_config(wiring: Wiring, config: u8) -> Wiring {
+    let three_wire = config & (1 << 4) != 0;
+    match (wiring, three_wire) {
+        (Wiring::ThreeWire, true) => wiring,
+        (_, true) => Wiring::ThreeWire,
+        (Wiring::ThreeWire, false) => Wiring::TwoWire,
+        (_, false) => wiring,
+    }
+}
+
+/// The contents of the configuration register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Config {
+    /// Enable the V_BIAS voltage, which is required to correctly perform conversion.
+    pub vbias: bool,
+    /// Automatically perform conversion, otherwise normally off.
+    pub conversion_mode: bool,
+    /// Perform a single conversion.
+    pub one_shot: bool,
+    /// Whether a two, three or four wire sensor is used.
+    pub sensor_type: SensorType,
+    /// The mains frequency that should be filtered out.
+    pub filter_mode: FilterMode,
+}
+
+impl Default for Config {
+    /// V_BIAS on, automatic conversion, a three wire sensor and the 50Hz filter.
+    fn default() -> Self {
+        Config {
+            vbias: true,
+            conversion_mode: true,
+            one_shot: false,
+            sensor_type: SensorType::ThreeWire,
+            filter_mode: FilterMode::Filter50Hz,
+        }
+    }
+}
+
+impl Config {
+    /// The power on defaults of the configuration register, i.e. everything disabled,
+    /// a two or four wire sensor and the 60Hz filter.
+    /// 
+    /// This differs from `Config::default`, which enables V_BIAS and automatic
+    /// conversion for a three wire sensor with the 50Hz filter. Chain the setters to
+    /// build a configuration for `Max31865::configure_with`:
+    /// 
+    /// ```ignore
+    /// max31865.configure_with(Config::empty()
+    ///     .vbias(true)
+    ///     .conversion_mode(true)
+    ///     .sensor(SensorType::ThreeWire)
+    ///     .filter(FilterMode::Filter50Hz))?;
+    /// ```
+    pub fn empty() -> Config {
+        Config::from_bits(0)
+    }
+
+    /// The power on defaults of the configuration register, see `Config::empty`.
+    #[deprecated(note = "use `Config::empty`, which doesn't match `Config::default`")]
+    pub fn new() -> Config {
+        Config::empty()
+    }
+
+    /// Enable or disable the V_BIAS voltage.
+    pub fn vbias(self, vbias: bool) -> Config {
+        Config { vbias, ..self }
+    }
+
+    /// Enable or disable automatic conversion.
+    pub fn conversion_mode(self, conversion_mode: bool) -> Config {
+        Config { conversion_mode, ..self }
+    }
+
+    /// Start a single conversion.
+    pub fn one_shot(self, one_shot: bool) -> Config {
+        Config { one_shot, ..self }
+    }
+
+    /// Set the type of sensor wiring.
+    pub fn sensor(self, sensor_type: SensorType) -> Config {
+        Config { sensor_type, ..self }
+    }
+
+    /// Set the mains frequency filter.
+    pub fn filter(self, filter_mode: FilterMode) -> Config {
+        Config { filter_mode, ..self }
+    }
+
+    /// Encode the configuration as the value of the configuration register.
+    pub fn to_bits(&self) -> u8 {
+        ((self.vbias as u8) << 7) |
+            ((self.conversion_mode as u8) << 6) |
+            ((self.one_shot as u8) << 5) |
+            ((self.sensor_type as u8) << 4) |
+            (self.filter_mode.to_bit() as u8)
+    }
+
+    /// Decode the value of the configuration register.
+    /// 
+    /// The fault detection and fault status clear bits are ignored.
+    pub fn from_bits(bits: u8) -> Config {
+        Config {
+            vbias: bits & (1 << 7) != 0,
+            conversion_mode: bits & (1 << 6) != 0,
+            one_shot: bits & (1 << 5) != 0,
+            sensor_type: if bits & (1 << 4) != 0 { SensorType::ThreeWire } else { SensorType::TwoOrFourWire },
+            filter_mode: FilterMode::from_bit(bits & 1 != 0),
+        }
+    }
+}
+
+/// Every bit of the configuration register, including the command bits `Config`
+/// ignores.
+/// 
+/// This is meant for debugging unusual states, e.g. a fault detection cycle that
+/// never finishes. Use `Config` to configure the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigBits {
+    /// D7, V_BIAS enabled.
+    pub vbias: bool,
+    /// D6, automatic conversion.
+    pub conversion_mode: bool,
+    /// D5, one shot conversion in progress.
+    pub one_shot: bool,
+    /// D4, three wire sensor.
+    pub three_wire: bool,
+    /// D3, the first fault detection cycle bit.
+    pub fault_detection_d3: bool,
+    /// D2, the second fault detection cycle bit.
+    pub fault_detection_d2: bool,
+    /// D1, fault status clear.
+    pub fault_status_clear: bool,
+    /// D0, the 50Hz filter.
+    pub filter_50hz: bool,
+}
+
+impl ConfigBits {
+    /// Decode the value of the configuration register.
+    pub fn from_bits(bits: u8) -> ConfigBits {
+        ConfigBits {
+            vbias: bits & (1 << 7) != 0,
+            conversion_mode: bits & (1 << 6) != 0,
+            one_shot: bits & (1 << 5) != 0,
+            three_wire: bits & (1 << 4) != 0,
+            fault_detection_d3: bits & (1 << 3) != 0,
+            fault_detection_d2: bits & (1 << 2) != 0,
+            fault_status_clear: bits & (1 << 1) != 0,
+            filter_50hz: bits & 1 != 0,
+        }
+    }
+
+    /// Encode the bits as the value of the configuration register.
+    pub fn to_bits(&self) -> u8 {
+        ((self.vbias as u8) << 7) |
+            ((self.conversion_mode as u8) << 6) |
+            ((self.one_shot as u8) << 5) |
+            ((self.three_wire as u8) << 4) |
+            ((self.fault_detection_d3 as u8) << 3) |
+            ((self.fault_detection_d2 as u8) << 2) |
+            ((self.fault_status_clear as u8) << 1) |
+            (self.filter_50hz as u8)
+    }
+}
+
+/// The type of platinum RTD element connected to the MAX31865.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RtdType {
+    /// 100 Ohms at 0 degrees Celcius.
+    Pt100,
+    /// 1000 Ohms at 0 degrees Celcius.
+    Pt1000,
+}
+
+impl RtdType {
+    /// The nominal resistance of the element at 0 degrees Celcius in Ohms.
+    pub fn nominal_resistance(&self) -> u32 {
+        match self {
+            RtdType::Pt100 => 100,
+            RtdType::Pt1000 => 1000,
+        }
+    }
+
+    /// The default calibration value for the element, i.e. the reference
+    /// resistance in Ohms multiplied by 100 of a typical board: 400 Ohms for
+    /// a PT100 and 4300 Ohms for a PT1000.
+    pub fn default_calibration(&self) -> u32 {
+        match self {
+            RtdType::Pt100 => 40000,
+            RtdType::Pt1000 => 430000,
+        }
+    }
+
+    /// Classify an element of this type by its resistance at a known temperature, see
+    /// `RtdClass`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `measured_ohms` - The measured resistance of the element in Ohms.
+    /// * `reference_temp_c` - The temperature of the element in degrees Celcius, e.g.
+    ///                        of a calibration bath or an ice point.
+    /// 
+    /// # Remarks
+    /// 
+    /// The resistance is compared against the nominal resistance at the temperature
+    /// plus and minus the tolerance of each class, from the tightest to the loosest.
+    pub fn classify(&self, measured_ohms: f32, reference_temp_c: f32) -> RtdClass {
+        let r0 = self.nominal_resistance() as f32;
+        [RtdClass::ThirdDin, RtdClass::ClassA, RtdClass::ClassB]
+            .into_iter()
+            .find(|class| {
+                let tolerance = class.tolerance_c(reference_temp_c).unwrap_or(0.0);
+                let low = temp_conversion::temp_to_resistance(reference_temp_c - tolerance, r0);
+                let high = temp_conversion::temp_to_resistance(reference_temp_c + tolerance, r0);
+                (low..=high).contains(&measured_ohms)
+            })
+            .unwrap_or(RtdClass::OutOfTolerance)
+    }
+}
+
+/// The tolerance class of a platinum RTD element as specified by IEC 60751.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RtdClass {
+    /// 1/3 DIN, a third of the class B tolerance.
+    ThirdDin,
+    /// Class A, 0.15 + 0.002 * |t| degrees Celcius.
+    ClassA,
+    /// Class B, 0.3 + 0.005 * |t| degrees Celcius.
+    ClassB,
+    /// Outside of the class B tolerance.
+    OutOfTolerance,
+}
+
+impl RtdClass {
+    /// The permitted deviation in degrees Celcius at the temperature `celsius`, or
+    /// `None` for `OutOfTolerance`.
+    pub fn tolerance_c(&self, celsius: f32) -> Option<f32> {
+        let t = celsius.abs();
+        match self {
+            RtdClass::ThirdDin => Some((0.3 + 0.005 * t) / 3.0),
+            RtdClass::ClassA => Some(0.15 + 0.002 * t),
+            RtdClass::ClassB => Some(0.3 + 0.005 * t),
+            RtdClass::OutOfTolerance => None,
+        }
+    }
+}
+
+/// The decoded contents of the fault status register.
+/// 
+/// Each field corresponds to one bit of the register, see the fault status
+/// register description in the datasheet for possible causes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FaultStatus {
+    /// The RTD resistance is above the high fault threshold (D7).
+    pub rtd_high_threshold: bool,
+    /// The RTD resistance is below the low fault threshold (D6).
+    pub rtd_low_threshold: bool,
+    /// REFIN- is greater than 0.85 x V_BIAS (D5).
+    /// 
+    /// Almost no current flows through the reference resistor, so REFIN- stays close
+    /// to V_BIAS. Usually the RTD element or its FORCE+/FORCE- leads are open, or
+    /// REFIN- is shorted to REFIN+. Checked by the fault detection cycle, see
+    /// `Max31865::run_fault_detection`.
+    pub refin_high: bool,
+    /// REFIN- is less than 0.85 x V_BIAS while FORCE- is open (D4).
+    /// 
+    /// The fault detection cycle opens the FORCE- switch, which should stop the
+    /// current through the reference resistor. If REFIN- is still pulled down the
+    /// current leaks elsewhere, usually because REFIN-, FORCE2 or RTDIN+ is shorted to
+    /// ground or to FORCE-.
+    pub refin_low: bool,
+    /// RTDIN- is less than 0.85 x V_BIAS while FORCE- is open (D3).
+    /// 
+    /// Like `refin_low`, but for the bottom of the RTD: RTDIN- is pulled down while
+    /// FORCE- is open, usually because RTDIN- is shorted to ground or the RTD element
+    /// is shorted.
+    pub rtdin_low: bool,
+    /// Overvoltage or undervoltage fault (D2).
+    /// 
+    /// Set when one of the protected inputs (FORCE+, FORCE2, RTDIN+, RTDIN-, FORCE-)
+    /// is above V_DD or below ground, e.g. because RTD+ is shorted to V_DD or a wire
+    /// is shorted to ground. This usually indicates a wiring problem rather than a
+    /// temperature out of range, unlike the threshold faults.
+    pub over_under_voltage: bool,
+}
+
+impl FaultStatus {
+    /// Decode the value of the fault status register.
+    pub fn from_bits(bits: u8) -> FaultStatus {
+        FaultStatus {
+            rtd_high_threshold: bits & (1 << 7) != 0,
+            rtd_low_threshold: bits & (1 << 6) != 0,
+            refin_high: bits & (1 << 5) != 0,
+            refin_low: bits & (1 << 4) != 0,
+            rtdin_low: bits & (1 << 3) != 0,
+            over_under_voltage: bits & (1 << 2) != 0,
+        }
+    }
+
+    /// Whether all fault bits are clear.
+    pub fn is_ok(&self) -> bool {
+        self.descriptions().iter().all(|&(active, _)| !active)
+    }
+}
+
+impl FaultStatus {
+    // the description of each fault, in register order
+    fn descriptions(&self) -> [(bool, &'static str); 6] {
+        [
+            (self.rtd_high_threshold, "RTD High Threshold"),
+            (self.rtd_low_threshold, "RTD Low Threshold"),
+            (self.refin_high, "REFIN- > 0.85 x V_BIAS"),
+            (self.refin_low, "REFIN- < 0.85 x V_BIAS (FORCE- open)"),
+            (self.rtdin_low, "RTDIN- < 0.85 x V_BIAS (FORCE- open)"),
+            (self.over_under_voltage, "Overvoltage/Undervoltage"),
+        ]
+    }
+
+    // write the name of each active fault separated by `; `, or `No fault` if none is
+    // set, shared by the `core::fmt` and `ufmt` implementations
+    fn write_names<Err>(&self, mut write: impl FnMut(&str) -> Result<(), Err>)
+        -> Result<(), Err>
+    {
+        let mut first = true;
+        for (_, name) in self.descriptions().iter().filter(|(active, _)| *active) {
+            if !first {
+                write("; ")?;
+            }
+            write(name)?;
+            first = false;
+        }
+
+        if first {
+            write("No fault")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Display for FaultStatus {
+    /// Lists each active fault separated by `; `, or `No fault` if none is set.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.write_names(|name| f.write_str(name))
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for FaultStatus {
+    /// Same output as the `core::fmt::Display` implementation.
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        self.write_names(|name| f.write_str(name))
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for FaultStatus {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        f.debug_struct("FaultStatus")?
+            .field("rtd_high_threshold", &self.rtd_high_threshold)?
+            .field("rtd_low_threshold", &self.rtd_low_threshold)?
+            .field("refin_high", &self.refin_high)?
+            .field("refin_low", &self.refin_low)?
+            .field("rtdin_low", &self.rtdin_low)?
+            .field("over_under_voltage", &self.over_under_voltage)?
+            .finish()
+    }
+}
+
+/// A single RTD reading, see `Max31865::read_all`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Reading {
+    /// The combined RTD registers, including the fault bit, as returned by `read_raw`.
+    pub raw: u16,
+    /// The resistance of the RTD element in Ohms.
+    pub resistance_ohms: f32,
+    /// The temperature in degrees Celcius, using the lookup table conversion if the
+    /// `lookup-table` feature is enabled, otherwise the Callendar-Van Dusen equation.
+    pub temperature_c: f32,
+    /// Whether the fault bit of the RTD registers was set.
+    pub fault: bool,
+    /// The channel of the driver that took the reading, see `Max31865::set_channel`.
+    pub channel: u8,
+    /// The time the reading was taken, see `Max31865::read_all_at`.
+    pub timestamp: Option<u64>,
+}
+
+/// Whether a resistance was outside of the range of the temperature conversion, see
+/// `temp_conversion::temperature_range`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Saturation {
+    /// The resistance was inside the range.
+    InRange,
+    /// The resistance was below the range, the temperature is clamped to its minimum.
+    Low,
+    /// The resistance was above the range, the temperature is clamped to its maximum.
+    High,
+}
+
+/// Everything about a single conversion, see `Max31865::read_sample`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sample {
+    /// The temperature in degrees Celcius multiplied by 100, as returned by
+    /// `read_default_conversion`.
+    pub temperature_c: i32,
+    /// The fault status if the fault bit of the RTD registers was set.
+    pub fault: Option<FaultStatus>,
+    /// Whether the temperature was clamped to the range of the conversion.
+    pub saturated: Saturation,
+}
+
+/// The decoded contents of the RTD registers, see `Max31865::read_rtd_reading`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RtdReading {
+    /// The 15 bit ratio of the RTD resistance to the reference resistance.
+    pub ratio: u16,
+    /// Whether the fault bit (D0) was set.
+    pub fault: bool,
+}
+
+impl RtdReading {
+    /// Decode the combined value of the RTD registers as returned by `read_raw`.
+    pub fn from_raw(raw: u16) -> RtdReading {
+        RtdReading {
+            ratio: raw_to_ratio(raw),
+            fault: raw & 1 != 0,
+        }
+    }
+}
+
+/// The state of the fault thresholds, see `Max31865::read_alert_state`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlertState {
+    /// The RTD resistance was above the high fault threshold.
+    pub high: bool,
+    /// The RTD resistance was below the low fault threshold.
+    pub low: bool,
+}
+
+impl AlertState {
+    /// Whether either threshold was crossed.
+    pub fn is_active(&self) -> bool {
+        self.high || self.low
+    }
+}
+
+/// The settings of a device, see `Max31865::snapshot`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceState {
+    /// The contents of the configuration register.
+    pub config: Config,
+    /// The physical wiring of the sensor.
+    pub wiring: Wiring,
+    /// The 15 bit high fault threshold.
+    pub high_fault_threshold: u16,
+    /// The 15 bit low fault threshold.
+    pub low_fault_threshold: u16,
+    /// The type of RTD element.
+    pub rtd_type: RtdType,
+    /// The calibration value, i.e. the reference resistance in Ohms multiplied by 100.
+    pub calibration: u32,
+}
+
+/// Placeholder for the ready pin when it isn't connected.
+/// 
+/// A driver without a ready pin doesn't provide `is_ready` and `wait_for_ready`.
+/// Use `read_one_shot`, which waits a fixed conversion time, instead.
+#[derive(Debug, Clone, Copy)]
+pub struct NoReady;
+
+/// The MAX31865 driver.
+/// 
+/// `R0` fixes the nominal resistance of the RTD element in the type, `0` leaves it
+/// to be selected at runtime with `set_rtd_type`. See `Max31865Pt100` and
+/// `Max31865Pt1000`, which are returned by `new_pt100` and `new_pt1000`.
+#[derive(Debug)]
+pub struct Max31865<SPI, RDY = NoReady, const R0: u32 = 0> {
     spi: SPI,
-    ncs: NCS,
     rdy: RDY,
+    config: u8,
+    wiring: Wiring,
+    params: ConversionParams,
+    channel: u8,
+    last_raw: Option<u16>,
+    sample_count: u8,
+    one_shot_pending: bool,
+    mode: Mode,
+}
+
+/// A driver fixed to a PT100 element, see `Max31865::new_pt100`.
+/// 
+/// The RTD type is part of the type, so `set_rtd_type` isn't available and a driver
+/// for a PT1000 can't be passed where this one is expected.
+pub type Max31865Pt100<SPI, RDY = NoReady> = Max31865<SPI, RDY, 100>;
+
+/// A driver fixed to a PT1000 element, see `Max31865::new_pt1000` and `Max31865Pt100`.
+pub type Max31865Pt1000<SPI, RDY = NoReady> = Max31865<SPI, RDY, 1000>;
+
+/// Compute the calibration value for a PT100 from a raw reading at 100 degrees Celcius.
+/// 
+/// # Arguments
+/// 
+/// * `raw_at_100c` - The value returned by `read_raw` while the sensor is at 100 degrees
+///                   Celcius.
+/// 
+/// # Remarks
+/// 
+/// This calculates `(13851 << 15) / (raw >> 1)`, i.e. the reference resistance in Ohms
+/// multiplied by 100 at which the reading corresponds to 138.51 Ohms. Multiply the
+/// result by 10 for a PT1000.
+pub fn compute_calibration(raw_at_100c: u16) -> u32 {
+    let ratio = raw_to_ratio(raw_at_100c).max(1) as u32;
+    (13851 << 15) / ratio
+}
+
+// the one shot (D5), fault detection (D3, D2) and fault status clear (D1) bits
+// of the configuration register are commands rather than settings, so they are
+// never kept in the cached configuration
+const CONFIG_SETTINGS: u8 = 0b1101_0001;
+
+// the automatic fault detection cycle takes a few hundred microseconds, poll for
+// up to 10ms before giving up
+const FAULT_DETECTION_POLL_US: u32 = 100;
+const FAULT_DETECTION_POLLS: u32 = 100;
+
+// a conversion is available every 20ms at the latest in automatic conversion mode,
+// poll the ready pin for up to 100ms to also cover the first conversion
+const READINGS_POLL_US: u32 = 1_000;
+const READINGS_POLLS: u32 = 100;
+
+// the RTD and threshold registers hold the 15 bit ratio left shifted by one, with the
+// fault bit (D0) of the RTD registers in the lowest bit, which the shift drops
+fn raw_to_ratio(raw: u16) -> u16 {
+    raw >> 1
+}
+
+// the inverse of `raw_to_ratio`, with the lowest bit clear
+fn ratio_to_raw(ratio: u16) -> u16 {
+    ratio << 1
+}
+
+fn reference_to_calibration(ohms: f32) -> u32 {
+    (ohms * 100.0 + 0.5) as u32
+}
+
+/// A linear correction of the measured resistance, see
+/// `Max31865::set_two_point_calibration`.
+#[derive(Debug, Clone, Copy)]
+struct Correction {
+    // the gain as a 16.16 fixed-point value
+    gain: u32,
+    // the offset in Ohms multiplied by 100
+    offset: i32,
+}
+
+impl Correction {
+    const NONE: Correction = Correction { gain: 1 << 16, offset: 0 };
+
+    /// Compute the correction mapping the measured resistances to the expected ones.
+    fn two_point(measured_low: f32, expected_low: f32, measured_high: f32,
+        expected_high: f32) -> Option<Correction> {
+        let span = measured_high - measured_low;
+        if span <= 0.0 {
+            return None;
+        }
+
+        let gain = (expected_high - expected_low) / span;
+        // anything far from 1 is a bad measurement rather than a correction
+        if !(0.5..=1.5).contains(&gain) {
+            return None;
+        }
+        let offset = (expected_low - gain * measured_low) * 100.0;
+        let offset = if offset < 0.0 { offset - 0.5 } else { offset + 0.5 };
+
+        Some(Correction {
+            gain: (gain * 65536.0 + 0.5) as u32,
+            offset: offset as i32,
+        })
+    }
+
+    /// Correct a resistance in Ohms multiplied by 100.
+    #[cfg(feature = "lookup-table")]
+    fn apply_centi(&self, ohms: u64) -> u64 {
+        let ohms = ((ohms * self.gain as u64) >> 16) as i64 + self.offset as i64;
+        ohms.max(0) as u64
+    }
+
+    /// The inverse of `apply_centi`.
+    fn invert_centi(&self, ohms: u64) -> u64 {
+        let ohms = (ohms as i64 - self.offset as i64).max(0) as u64;
+        (ohms << 16) / self.gain as u64
+    }
+
+    /// Correct a resistance in Ohms.
+    fn apply(&self, ohms: f32) -> f32 {
+        ohms * self.gain as f32 / 65536.0 + self.offset as f32 / 100.0
+    }
+}
+
+/// A calibration value, i.e. the reference resistance in Ohms multiplied by 100.
+/// 
+/// The unit is part of the type so that e.g. 400 can't be passed by accident where
+/// 40000 was meant, which would make every reading wrong by a factor of 100.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Calibration(u32);
+
+impl Calibration {
+    /// The calibration for a reference resistance in Ohms, e.g. `from_ohms(400.0)`.
+    pub fn from_ohms(ohms: f32) -> Calibration {
+        Calibration(reference_to_calibration(ohms))
+    }
+
+    /// The calibration for a reference resistance in Ohms multiplied by 100, e.g. the
+    /// value returned by `compute_calibration` or `Max31865::calibration`.
+    pub fn from_centi_ohms(centi_ohms: u32) -> Calibration {
+        Calibration(centi_ohms)
+    }
+
+    /// The reference resistance in Ohms multiplied by 100.
+    pub fn centi_ohms(&self) -> u32 {
+        self.0
+    }
+}
+
+/// The resistance of the reference resistor in Ohms.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReferenceResistance(f32);
+
+impl ReferenceResistance {
+    /// The reference resistance in Ohms, e.g. `from_ohms(430.0)`.
+    pub fn from_ohms(ohms: f32) -> ReferenceResistance {
+        ReferenceResistance(ohms)
+    }
+
+    /// The reference resistance in Ohms.
+    pub fn ohms(&self) -> f32 {
+        self.0
+    }
+}
+
+/// How the integer conversion rounds the resistance computed from the RTD ratio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rounding {
+    /// Truncate toward zero, which reads on average half an LSB of the resistance low.
+    Truncate,
+    /// Round to the nearest value.
+    Nearest,
+}
+
+/// The parameters converting the RTD registers to a resistance and temperature.
+/// 
+/// # Remarks
+/// 
+/// The driver holds one of these, see `Max31865::conversion_params`. They can also be
+/// constructed directly to convert logged raw values offline, e.g. on a host.
+/// 
+/// The read methods of the driver fetch the raw value and convert it with these
+/// parameters, so `to_sample`, `to_temperature` and `to_resistance` return exactly
+/// what `read_sample`, `read_default_conversion` and `read_resistance` would for the
+/// same raw value.
+#[derive(Debug, Clone, Copy)]
+pub struct ConversionParams {
     calibration: u32,
+    reference_ohms: f32,
+    rtd_type: RtdType,
+    correction: Correction,
+    conversion: Option<fn(f32) -> f32>,
+    offset: i32,
+    rounding: Rounding,
+    linearization: Option<[f32; 3]>,
 }
 
+impl ConversionParams {
+    /// The default parameters for the type of RTD element, see
+    /// `RtdType::default_calibration`.
+    pub fn new(rtd_type: RtdType) -> ConversionParams {
+        ConversionParams::with_calibration(rtd_type, rtd_type.default_calibration())
+    }
 
-impl<E, SPI, NCS, RDY> Max31865<SPI, NCS, RDY>
-where 
-    SPI: spi::Write<u8, Error = E> + spi::Transfer<u8, Error = E>,
-    NCS: OutputPin,
-    RDY: InputPin
-{
-    /// Create a new MAX31865 module.
+    /// The parameters for the given calibration value, i.e. the reference resistance
+    /// in Ohms multiplied by 100, see `Max31865::set_calibration`.
+    pub fn with_calibration(rtd_type: RtdType, calib: u32) -> ConversionParams {
+        ConversionParams {
+            calibration: calib,
+            reference_ohms: calib as f32 / 100.0,
+            rtd_type,
+            correction: Correction::NONE,
+            conversion: None,
+            offset: 0,
+            rounding: Rounding::Truncate,
+            linearization: None,
+        }
+    }
+
+    /// The parameters for the given reference resistance in Ohms, see
+    /// `Max31865::set_reference_resistance`.
+    pub fn with_reference_resistance(rtd_type: RtdType, ohms: f32) -> ConversionParams {
+        ConversionParams {
+            calibration: reference_to_calibration(ohms),
+            reference_ohms: ohms,
+            rtd_type,
+            correction: Correction::NONE,
+            conversion: None,
+            offset: 0,
+            rounding: Rounding::Truncate,
+            linearization: None,
+        }
+    }
+
+    /// The calibration value, i.e. the reference resistance in Ohms multiplied by 100.
+    pub fn calibration(&self) -> u32 {
+        self.calibration
+    }
+
+    /// The reference resistance in Ohms.
+    pub fn reference_ohms(&self) -> f32 {
+        self.reference_ohms
+    }
+
+    /// The type of RTD element.
+    pub fn rtd_type(&self) -> RtdType {
+        self.rtd_type
+    }
+
+    /// Replace the built-in temperature conversion with a custom one.
     /// 
     /// # Arguments
     /// 
-    /// * `spi` - The SPI module to communicate on.
-    /// * `ncs` - The chip select pin which should be set to a push pull output pin.
-    /// * `rdy` - The ready pin which is set low by the MAX31865 controller whenever
-    ///             it has finished converting the output.
+    /// * `conversion` - Converts the resistance of the element in Ohms to degrees
+    ///                  Celcius, or `None` to use the built-in conversion again.
     /// 
-    pub fn new(
-        spi: SPI,
-        mut ncs: NCS,
-        rdy: RDY,
-    ) -> Result<Max31865<SPI, NCS, RDY>, E>
-    {
-        let default_calib = 40000;
+    /// # Remarks
+    /// 
+    /// This allows proprietary linearizations or non-platinum elements while still
+    /// using the register access of the driver. The measured resistance, including the
+    /// two-point correction, is passed to the conversion for `to_temperature`,
+    /// `to_temperature_c` and `Reading::temperature_c`. The temperature based threshold setters such as
+    /// `Max31865::set_high_fault_threshold_temp` can't invert it and keep using the
+    /// built-in conversion.
+    pub fn with_conversion(self, conversion: Option<fn(f32) -> f32>) -> ConversionParams {
+        ConversionParams { conversion, ..self }
+    }
+
+    /// The custom temperature conversion, see `with_conversion`.
+    pub fn conversion(&self) -> Option<fn(f32) -> f32> {
+        self.conversion
+    }
+
+    /// Shift the converted temperatures by a fixed offset.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `centi_c` - The offset in degrees Celcius multiplied by 100, e.g. -50 to
+    ///               report 0.5 degrees Celcius less.
+    /// 
+    /// # Remarks
+    /// 
+    /// The offset is added after the conversion in `to_temperature` and
+    /// `Reading::temperature_c`, and subtracted from the temperature based
+    /// thresholds. It is independent of the calibration and the two-point correction,
+    /// which correct the resistance, and composes with them.
+    pub fn with_temperature_offset(self, centi_c: i32) -> ConversionParams {
+        ConversionParams { offset: centi_c, ..self }
+    }
+
+    /// The temperature offset in degrees Celcius multiplied by 100, see
+    /// `with_temperature_offset`.
+    pub fn temperature_offset(&self) -> i32 {
+        self.offset
+    }
+
+    /// Set how the resistance is rounded by the integer conversion.
+    /// 
+    /// # Remarks
+    /// 
+    /// The integer conversion of `to_temperature` computes the resistance in Ohms
+    /// multiplied by 100 as `ratio * calibration >> 15`. By default the shift
+    /// truncates, which biases the resistance down by about half an LSB, i.e. 0.005
+    /// Ohms or roughly 0.013 degrees Celcius for a PT100. `Rounding::Nearest` adds half
+    /// an LSB before the shift to remove the bias. The floating point conversions don't
+    /// round and are unaffected.
+    pub fn with_rounding(self, rounding: Rounding) -> ConversionParams {
+        ConversionParams { rounding, ..self }
+    }
+
+    /// The rounding of the integer conversion, see `with_rounding`.
+    pub fn rounding(&self) -> Rounding {
+        self.rounding
+    }
+
+    /// Correct the nonlinearity of the ADC.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `coeffs` - The coefficients `[c0, c1, c2]` of the error of the ADC in LSBs of
+    ///              the 15 bit ratio, or `None` to disable the correction.
+    /// 
+    /// # Remarks
+    /// 
+    /// The ratio is corrected to `ratio - (c0 + c1 * x + c2 * x^2)` with `x` the ratio
+    /// normalized to 0..1, before the resistance is computed. The MAX31865 specifies
+    /// an integral nonlinearity of about 1 LSB, which is largest towards full scale.
+    /// For a PT100 with a 400 Ohm reference one LSB is about 0.03 degrees Celcius, so
+    /// this is only worth it with coefficients fitted against a precision resistance
+    /// decade. The correction is off by default. It uses floating point math, also in
+    /// `to_temperature`, and isn't applied to the temperature based thresholds.
+    pub fn with_adc_linearization(self, coeffs: Option<[f32; 3]>) -> ConversionParams {
+        ConversionParams { linearization: coeffs, ..self }
+    }
+
+    /// The coefficients of the ADC nonlinearity correction, see
+    /// `with_adc_linearization`.
+    pub fn adc_linearization(&self) -> Option<[f32; 3]> {
+        self.linearization
+    }
+
+    /// Keep the custom conversion, the temperature offset, the rounding and the ADC
+    /// linearization of `previous`, which aren't part of the calibration.
+    fn with_overrides_of(self, previous: &ConversionParams) -> ConversionParams {
+        ConversionParams {
+            conversion: previous.conversion,
+            offset: previous.offset,
+            rounding: previous.rounding,
+            linearization: previous.linearization,
+            ..self
+        }
+    }
+
+    /// Convert the combined RTD registers, as returned by `Max31865::read_raw`, to
+    /// degrees Celcius multiplied by 100.
+    /// 
+    /// # Remarks
+    /// 
+    /// This is the conversion of `Max31865::read_default_conversion`. The fault bit is
+    /// ignored.
+    #[cfg(feature = "lookup-table")]
+    pub fn to_temperature(&self, raw: u16) -> i32 {
+        self.ratio_to_temp(raw_to_ratio(raw))
+    }
+
+    /// Convert the combined RTD registers, as returned by `Max31865::read_raw`, to
+    /// degrees Celcius with the Callendar-Van Dusen equation.
+    /// 
+    /// # Remarks
+    /// 
+    /// This is the conversion of `Max31865::read_temperature_c`. Unlike `to_temperature`
+    /// the lookup table isn't used, but the ADC linearization, the two-point
+    /// correction, the custom conversion and the temperature offset are applied like
+    /// for every other reading. The fault bit is ignored. Requires the `float` feature.
+    #[cfg(feature = "float")]
+    pub fn to_temperature_c(&self, raw: u16) -> f32 {
+        self.float_temperature(self.to_resistance(raw))
+    }
+
+    /// Convert the combined RTD registers, as returned by `Max31865::read_raw`, to the
+    /// resistance of the RTD element in Ohms, see `Max31865::read_resistance`.
+    pub fn to_resistance(&self, raw: u16) -> f32 {
+        self.ratio_to_resistance(raw_to_ratio(raw))
+    }
+
+    /// The change in degrees Celcius corresponding to one LSB of the 15 bit ratio.
+    /// 
+    /// # Remarks
+    /// 
+    /// One LSB is `reference_ohms / 32768` Ohms, which is divided by the slope of the
+    /// element around 0 degrees Celcius, e.g. about 0.031 degrees Celcius for a PT100
+    /// with a 400 Ohm reference. The slope decreases slightly towards high
+    /// temperatures, so the resolution gets a little coarser there.
+    pub fn temperature_resolution_c(&self) -> f32 {
+        let r0 = self.rtd_type.nominal_resistance() as f32;
+        let lsb = self.ratio_to_resistance(1) - self.ratio_to_resistance(0);
+        let slope = temp_conversion::temp_to_resistance(1.0, r0)
+            - temp_conversion::temp_to_resistance(0.0, r0);
+
+        lsb / slope
+    }
+
+    /// Convert a 15 bit RTD ratio to a resistance in Ohms.
+    fn ratio_to_resistance(&self, ratio: u16) -> f32 {
+        self.correction.apply(self.linearize(ratio) / 32768.0 * self.reference_ohms)
+    }
+
+    /// Apply the ADC linearization, if any, to a 15 bit RTD ratio.
+    fn linearize(&self, ratio: u16) -> f32 {
+        let ratio = ratio as f32;
+        match self.linearization {
+            Some([c0, c1, c2]) => {
+                let x = ratio / 32768.0;
+                ratio - (c0 + c1 * x + c2 * x * x)
+            }
+            None => ratio,
+        }
+    }
+
+    /// Convert a 15 bit RTD ratio to degrees Celcius multiplied by 100 using the lookup
+    /// table.
+    #[cfg(feature = "lookup-table")]
+    fn ratio_to_temp(&self, ratio: u16) -> i32 {
+        if let Some(conversion) = self.conversion {
+            let temp = conversion(self.ratio_to_resistance(ratio)) * 100.0;
+            let temp = if temp < 0.0 { temp - 0.5 } else { temp + 0.5 } as i32;
+            return temp + self.offset;
+        }
+
+        let ratio = match self.linearization {
+            Some(_) => (self.linearize(ratio) + 0.5).clamp(0.0, 32767.0) as u16,
+            None => ratio,
+        };
+        let ohms = temp_conversion::ratio_to_ohms(ratio, self.calibration, self.rounding);
+        let ohms = self.correction.apply_centi(ohms);
+        temp_conversion::ohms_to_temp(ohms, self.rtd_type.nominal_resistance()) + self.offset
+    }
+
+    /// Convert the combined RTD registers and the fault status register to a `Sample`,
+    /// see `Max31865::read_sample`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `raw` - The combined RTD registers as returned by `Max31865::read_raw`.
+    /// * `fault_bits` - The fault status register, which is only decoded if the fault
+    ///                  bit of `raw` is set, e.g. as returned by
+    ///                  `Max31865::read_measurement_burst`.
+    /// 
+    /// # Remarks
+    /// 
+    /// This is the complete decoding of the driver, including the calibration, the
+    /// two-point correction, the ADC linearization, the rounding and the temperature
+    /// offset, without any SPI access. Together with `Max31865::conversion_params` it
+    /// can replay recorded raw values on a host and check the results.
+    /// 
+    /// Without the `lookup-table` feature the temperature is computed with the
+    /// Callendar-Van Dusen equation instead, which saturates the same way.
+    pub fn to_sample(&self, raw: u16, fault_bits: u8) -> Sample {
+        let fault = if raw & 1 != 0 { Some(FaultStatus::from_bits(fault_bits)) } else { None };
+        let (min, max) = temp_conversion::temperature_range();
+        let r0 = self.rtd_type.nominal_resistance() as f32;
+        let ohms = self.to_resistance(raw);
+        let saturated = if ohms < temp_conversion::temp_to_resistance(min, r0) {
+            Saturation::Low
+        } else if ohms > temp_conversion::temp_to_resistance(max, r0) {
+            Saturation::High
+        } else {
+            Saturation::InRange
+        };
+
+        #[cfg(feature = "lookup-table")]
+        let temperature_c = self.to_temperature(raw);
+        #[cfg(not(feature = "lookup-table"))]
+        let temperature_c = {
+            let temp = self.float_temperature(ohms) * 100.0;
+            (if temp < 0.0 { temp - 0.5 } else { temp + 0.5 }) as i32
+        };
+
+        Sample {
+            temperature_c,
+            fault,
+            saturated,
+        }
+    }
+
+    /// Decode the combined RTD registers into a `Reading`.
+    fn reading(&self, raw: u16, channel: u8) -> Reading {
+        let resistance_ohms = self.to_resistance(raw);
+        #[cfg(feature = "lookup-table")]
+        let temperature_c = self.to_temperature(raw) as f32 / 100.0;
+        #[cfg(not(feature = "lookup-table"))]
+        let temperature_c = self.float_temperature(resistance_ohms);
+
+        Reading {
+            raw,
+            resistance_ohms,
+            temperature_c,
+            fault: raw & 1 != 0,
+            channel,
+            timestamp: None,
+        }
+    }
+
+    /// Convert a resistance in Ohms to degrees Celcius with the Callendar-Van Dusen
+    /// equation, or the custom conversion, and apply the temperature offset.
+    #[cfg(feature = "float")]
+    fn float_temperature(&self, ohms: f32) -> f32 {
+        let temp = match self.conversion {
+            Some(conversion) => conversion(ohms),
+            None => temp_conversion::resistance_to_temp(ohms,
+                self.rtd_type.nominal_resistance() as f32),
+        };
+        temp + self.offset as f32 / 100.0
+    }
+
+    /// Convert a temperature in degrees Celcius to the closest 15 bit RTD ratio, or
+    /// `None` if the temperature or the ratio is out of range.
+    fn temp_to_ratio(&self, celsius: f32) -> Option<u16> {
+        let celsius = celsius - self.offset as f32 / 100.0;
+        let (min, max) = temp_conversion::temperature_range();
+        if !(min..=max).contains(&celsius) {
+            return None;
+        }
+
+        let r0 = self.rtd_type.nominal_resistance();
+        #[cfg(feature = "lookup-table")]
+        let ohms = {
+            let temp = if celsius < 0.0 { celsius * 100.0 - 0.5 } else { celsius * 100.0 + 0.5 };
+            temp_conversion::lookup_resistance(temp as i32) as u64 * r0 as u64 / 100
+        };
+        #[cfg(not(feature = "lookup-table"))]
+        let ohms = (temp_conversion::temp_to_resistance(celsius, r0 as f32) * 100.0 + 0.5) as u64;
+        let ohms = self.correction.invert_centi(ohms);
+        let calib = self.calibration as u64;
+        // inverse of `ohms = ratio * calibration >> 15`, rounded to the nearest value
+        let ratio = ((ohms << 15) + calib / 2) / calib;
+        if ratio > 0x7FFF {
+            return None;
+        }
+        Some(ratio as u16)
+    }
+
+    /// Add the two-point correction computed from two raw readings at known
+    /// temperatures, or `None` if the readings can't be corrected.
+    fn with_two_point(&self, raw_low: u16, temp_low: f32, raw_high: u16,
+        temp_high: f32) -> Option<ConversionParams> {
+        let measured = |raw: u16| self.linearize(raw_to_ratio(raw)) / 32768.0 * self.reference_ohms;
+        let r0 = self.rtd_type.nominal_resistance() as f32;
+
+        let correction = Correction::two_point(measured(raw_low),
+            temp_conversion::temp_to_resistance(temp_low, r0), measured(raw_high),
+            temp_conversion::temp_to_resistance(temp_high, r0))?;
+        Some(ConversionParams { correction, ..*self })
+    }
+}
+
+/// Scale a temperature in degrees Celcius and round it to an `i16`, or `None` if it
+/// doesn't fit.
+fn celsius_to_i16(celsius: f32, scale: f32) -> Option<i16> {
+    let temp = celsius * scale;
+    if temp.is_nan() {
+        return None;
+    }
+    // round half away from zero first, then check the rounded value exactly
+    let temp = if temp < 0.0 { temp - 0.5 } else { temp + 0.5 } as i32;
+    i16::try_from(temp).ok()
+}
+
+/// Convert degrees Celcius multiplied by 100 to a fixed-point value.
+#[cfg(all(feature = "lookup-table", feature = "fixed"))]
+fn centi_to_fixed(temp: i32) -> fixed::types::I16F16 {
+    fixed::types::I16F16::from_bits((((temp as i64) << 16) / 100) as i32)
+}
+
+// alternating bit patterns written by `check_connection`, which don't survive a
+// shifted read
+const CONNECTION_PATTERNS: [u16; 2] = [0x5555, 0x2AAA];
+
+// a ratio within this distance of either rail indicates an open or shorted element
+const RAIL_MARGIN: u16 = 32;
+
+/// Determine whether the element is connected from its ratio and fault status.
+fn sensor_connected(ratio: u16, faults: FaultStatus) -> bool {
+    let at_rail = !(RAIL_MARGIN..=0x7FFF - RAIL_MARGIN).contains(&ratio);
+    let wiring_fault = faults.refin_high || faults.refin_low || faults.rtdin_low
+        || faults.over_under_voltage;
+
+    !at_rail && !wiring_fault
+}
+
+/// Convert degrees Celcius to a `uom` thermodynamic temperature.
+#[cfg(feature = "uom")]
+fn celsius_to_uom(celsius: f32) -> uom::si::f32::ThermodynamicTemperature {
+    uom::si::f32::ThermodynamicTemperature::new::<uom::si::thermodynamic_temperature::degree_celsius>(celsius)
+}
+
+/// A builder for constructing and configuring a `Max31865` in one step.
+/// 
+/// # Example
+/// 
+/// ```ignore
+/// let max31865 = Max31865Builder::new()
+///     .vbias(true)
+///     .conversion_mode(true)
+///     .filter(FilterMode::Filter50Hz)
+///     .sensor(SensorType::ThreeWire)
+///     .reference_resistance(ReferenceResistance::from_ohms(430.0))
+///     .build(spi, rdy)?;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Max31865Builder {
+    config: Config,
+    wiring: Wiring,
+    rtd_type: RtdType,
+    reference: Option<(u32, f32)>,
+    channel: u8,
+    sample_count: u8,
+    conversion: Option<fn(f32) -> f32>,
+}
+
+impl Default for Max31865Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Max31865Builder {
+    /// Create a new builder with the power on defaults of the configuration
+    /// register and a PT100 element.
+    pub fn new() -> Self {
+        Max31865Builder {
+            config: Config::from_bits(0),
+            wiring: Wiring::TwoWire,
+            rtd_type: RtdType::Pt100,
+            reference: None,
+            channel: 0,
+            sample_count: 1,
+            conversion: None,
+        }
+    }
+
+    /// Enable or disable the V_BIAS voltage.
+    pub fn vbias(mut self, vbias: bool) -> Self {
+        self.config.vbias = vbias;
+        self
+    }
+
+    /// Enable or disable automatic conversion.
+    pub fn conversion_mode(mut self, conversion_mode: bool) -> Self {
+        self.config.conversion_mode = conversion_mode;
+        self
+    }
+
+    /// Set the type of sensor wiring.
+    pub fn sensor(mut self, sensor_type: SensorType) -> Self {
+        self.config.sensor_type = sensor_type;
+        self
+    }
+
+    /// Set the physical wiring of the sensor, see `Max31865::set_wiring`.
+    pub fn wiring(mut self, wiring: Wiring) -> Self {
+        self.config.sensor_type = wiring.sensor_type();
+        self.wiring = wiring;
+        self
+    }
+
+    /// Set the mains frequency filter.
+    pub fn filter(mut self, filter_mode: FilterMode) -> Self {
+        self.config.filter_mode = filter_mode;
+        self
+    }
+
+    /// Set the type of RTD element, see `Max31865::set_rtd_type`.
+    pub fn rtd_type(mut self, rtd_type: RtdType) -> Self {
+        self.rtd_type = rtd_type;
+        self
+    }
+
+    /// Set the reference resistance, see `Max31865::set_reference_resistance`.
+    pub fn reference_resistance(mut self, ohms: ReferenceResistance) -> Self {
+        self.reference = Some((reference_to_calibration(ohms.0), ohms.0));
+        self
+    }
+
+    /// Set the calibration value, see `Max31865::set_calibration`.
+    pub fn calibration(mut self, calib: Calibration) -> Self {
+        self.reference = Some((calib.0, calib.0 as f32 / 100.0));
+        self
+    }
+
+    /// Set the channel included in each `Reading`, see `Max31865::set_channel`.
+    pub fn channel(mut self, channel: u8) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    /// Set the number of conversions averaged by `read_one_shot`, see
+    /// `Max31865::set_sample_count`.
+    pub fn sample_count(mut self, samples: u8) -> Self {
+        self.sample_count = samples;
+        self
+    }
+
+    /// Set a custom temperature conversion, see `Max31865::set_conversion`.
+    pub fn conversion(mut self, conversion: fn(f32) -> f32) -> Self {
+        self.conversion = Some(conversion);
+        self
+    }
+
+    /// Create the driver and write the configuration register.
+    /// 
+    /// Pass `NoReady` as `rdy` if the ready pin isn't connected.
+    pub fn build<E, SPI, RDY>(self, spi: SPI, rdy: RDY) -> Result<Max31865<SPI, RDY>, Error<E>>
+    where
+        SPI: SpiDevice<u8, Error = E>,
+    {
+        let mut max31865 = Max31865::new(spi, rdy)?;
+        max31865.wiring = self.wiring;
+        max31865.channel = self.channel;
+        max31865.set_sample_count(self.sample_count);
+        max31865.set_conversion(self.conversion);
+        max31865.set_rtd_type(self.rtd_type);
+        if let Some((calib, ohms)) = self.reference {
+            max31865.params = ConversionParams {
+                calibration: calib,
+                reference_ohms: ohms,
+                ..max31865.params
+            };
+        }
+        max31865.configure_with(self.config)?;
+
+        Ok(max31865)
+    }
+}
+
+impl<E, SPI, RDY> Max31865<SPI, RDY>
+where 
+    SPI: SpiDevice<u8, Error = E>,
+{
+    /// Create a new MAX31865 module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `spi` - The SPI device to communicate on. Chip select is managed by the
+    ///             `SpiDevice` implementation, which allows sharing the bus with
+    ///             other devices (e.g. through `embedded-hal-bus`).
+    /// * `rdy` - The ready pin which is set low by the MAX31865 controller whenever
+    ///             it has finished converting the output. Pass `NoReady` if the pin
+    ///             isn't connected, see `new_without_ready`.
+    /// 
+    /// # Remarks
+    /// 
+    /// The configuration register is assumed to hold its power-on value until
+    /// `configure` is called. Call `refresh_config` if the device was already
+    /// configured, e.g. after a reset of the microcontroller only.
+    pub fn new(
+        spi: SPI,
+        rdy: RDY,
+    ) -> Result<Max31865<SPI, RDY>, Error<E>>
+    {
+        let max31865 = Max31865 {
+            spi,
+            rdy,
+            config: 0, /* power-on reset value of the configuration register */
+            wiring: Wiring::TwoWire,
+            params: ConversionParams::new(RtdType::Pt100),
+            channel: 0,
+            last_raw: None,
+            sample_count: 1,
+            one_shot_pending: false,
+            mode: MODE,
+        };
+
+        Ok(max31865)
+    }
+
+    /// Create a new MAX31865 module and record the SPI mode the bus is configured with.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `spi` - The SPI device to communicate on, see `new`.
+    /// * `rdy` - The ready pin, see `new`.
+    /// * `mode` - The SPI mode of `spi`, either `MODE_1` or `MODE_3`.
+    /// 
+    /// # Remarks
+    /// 
+    /// `SpiDevice` doesn't expose the mode, so it can't be checked on the bus itself.
+    /// Returns `Error::UnsupportedMode` for modes 0 and 2, which the MAX31865 doesn't
+    /// support. Otherwise `mode` returns it later, e.g. to include it in a bug report
+    /// next to `check_connection`.
+    pub fn new_with_mode(spi: SPI, rdy: RDY, mode: Mode) -> Result<Max31865<SPI, RDY>, Error<E>> {
+        if mode != MODE_1 && mode != MODE_3 {
+            return Err(Error::UnsupportedMode);
+        }
+        let mut max31865 = Max31865::new(spi, rdy)?;
+        max31865.mode = mode;
+
+        Ok(max31865)
+    }
+
+    /// Create a new MAX31865 module for a PT100 element.
+    /// 
+    /// # Remarks
+    /// 
+    /// Uses the same defaults as `new`, a PT100 with a 400 Ohm reference resistor, but
+    /// fixes the element in the type, see `Max31865Pt100`.
+    pub fn new_pt100(spi: SPI, rdy: RDY) -> Result<Max31865Pt100<SPI, RDY>, Error<E>> {
+        Ok(Max31865::new(spi, rdy)?.into_fixed())
+    }
+
+    /// Create a new MAX31865 module for a PT1000 element.
+    /// 
+    /// # Remarks
+    /// 
+    /// The conversion uses the nominal resistance of 1000 Ohms and the default 4300 Ohm
+    /// reference resistor, see `RtdType::default_calibration`. Like calling
+    /// `set_rtd_type(RtdType::Pt1000)` after `new`, but the element is fixed in the
+    /// type, see `Max31865Pt1000`.
+    pub fn new_pt1000(spi: SPI, rdy: RDY) -> Result<Max31865Pt1000<SPI, RDY>, Error<E>> {
+        let mut max31865 = Max31865::new(spi, rdy)?;
+        max31865.set_rtd_type(RtdType::Pt1000);
+
+        Ok(max31865.into_fixed())
+    }
+
+    /// Create a new MAX31865 module with a known calibration value.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `spi` - The SPI device to communicate on, see `new`.
+    /// * `rdy` - The ready pin, see `new`.
+    /// * `calib` - The calibration value, see `set_calibration`.
+    /// 
+    /// # Remarks
+    /// 
+    /// This avoids repeating `calibrate_at_100c` on every boot: store the value
+    /// returned by `calibration` in non-volatile memory once and pass it here through
+    /// `Calibration::from_centi_ohms`.
+    pub fn new_with_calibration(
+        spi: SPI,
+        rdy: RDY,
+        calib: Calibration,
+    ) -> Result<Max31865<SPI, RDY>, Error<E>>
+    {
+        let mut max31865 = Max31865::new(spi, rdy)?;
+        max31865.params = ConversionParams::with_calibration(RtdType::Pt100, calib.centi_ohms());
+
+        Ok(max31865)
+    }
+
+    /// Set the type of RTD element connected to the device.
+    /// 
+    /// # Remarks
+    /// 
+    /// This also resets the calibration to the default reference resistance for
+    /// the element, see `RtdType::default_calibration`. Call `set_calibration`
+    /// afterwards if the board uses a different reference resistor.
+    /// 
+    /// Only available if the element isn't fixed in the type, see `Max31865Pt100`.
+    pub fn set_rtd_type(&mut self, rtd_type: RtdType) {
+        self.params = ConversionParams::new(rtd_type).with_overrides_of(&self.params);
+    }
+
+    /// Fix the RTD type of the driver in its type, see `Max31865Pt100`.
+    fn into_fixed<const T: u32>(self) -> Max31865<SPI, RDY, T> {
+        let Max31865 { spi, rdy, config, wiring, params, channel, last_raw, sample_count,
+            one_shot_pending, mode } = self;
+        Max31865 { spi, rdy, config, wiring, params, channel, last_raw, sample_count,
+            one_shot_pending, mode }
+    }
+}
+
+impl<E, SPI, RDY, const R0: u32> Max31865<SPI, RDY, R0>
+where 
+    SPI: SpiDevice<u8, Error = E>,
+{
+    /// The SPI mode passed to `new_with_mode`, or `MODE` for the other constructors.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// The ready pin passed to `new`.
+    /// 
+    /// # Remarks
+    /// 
+    /// The MAX31865 pulls the ready pin low whenever a conversion is finished, so
+    /// instead of polling `is_ready` the falling edge can be used as an interrupt
+    /// source. Use this, or `ready_pin_mut`, to configure the interrupt (e.g. EXTI or
+    /// GPIOTE) without keeping a second handle to the pin. Reading the RTD registers
+    /// returns the pin to high, which clears the interrupt condition.
+    pub fn ready_pin(&self) -> &RDY {
+        &self.rdy
+    }
+
+    /// Mutable access to the ready pin, see `ready_pin`.
+    pub fn ready_pin_mut(&mut self) -> &mut RDY {
+        &mut self.rdy
+    }
+
+    /// Updates the devices configuration.
+    /// 
+    /// # Arguments 
+    /// * `vbias` - Set to `true` to enable V_BIAS voltage, which is required to correctly perform conversion.Clone
+    /// * `conversion_mode` - `true` to automatically perform conversion, otherwise normally off.
+    /// * `one_shot` - Only perform detection once if set to `true`, otherwise repeats conversion.
+    /// * `sensor_type` - Define whether a two, three or four wire sensor is used.Clone
+    /// * `filter_mode` - Specify the mains frequency that should be used to filter out noise, e.g. 50Hz in Europe.
+    /// 
+    /// # Remarks
+    /// 
+    /// This will update the configuration register of the MAX31865 register. If the device doesn't properly react
+    /// to this, add a delay after calling `new` to give the chip time to power up.
+    /// 
+    /// The filter mode must not be changed while automatic conversion is enabled, in which
+    /// case `Error::FilterChange` is returned. Use `set_filter` to change it safely.
+    /// 
+    /// *Note*: The correct sensor configuration also requires changes to the PCB! Make sure to read the datasheet 
+    /// concerning this.
+    #[deprecated(note = "use `configure_with` with `Config::empty` instead")]
+    pub fn configure(&mut self, vbias: bool, conversion_mode: bool, one_shot: bool,
+        sensor_type: SensorType, filter_mode: FilterMode) -> Result<(), Error<E>> {
+        self.configure_with(Config {
+            vbias,
+            conversion_mode,
+            one_shot,
+            sensor_type,
+            filter_mode,
+        })
+    }
+
+    /// Updates the devices configuration, see `configure` for details.
+    pub fn configure_with(&mut self, config: Config) -> Result<(), Error<E>> {
+        if self.config & (1 << 6) != 0 && (self.config ^ config.to_bits()) & 1 != 0 {
+            return Err(Error::FilterChange);
+        }
+
+        self.write(Register::CONFIG, config.to_bits())?;
+        self.cache_config(config.to_bits());
+        Ok(())
+    }
+
+    /// Configure the device with `Config::default`.
+    /// 
+    /// # Remarks
+    /// 
+    /// This enables V_BIAS and automatic conversion for a three wire sensor with the
+    /// 50Hz filter. Together with the default `RtdType::Pt100` this covers the most
+    /// common setup, use `configure` for anything else.
+    pub fn configure_default(&mut self) -> Result<(), Error<E>> {
+        self.configure_with(Config::default())
+    }
+
+    /// Change the filter mode, preserving all other settings.
+    /// 
+    /// # Remarks
+    /// 
+    /// If automatic conversion is enabled, it is disabled while the filter is changed
+    /// and enabled again with a separate write afterwards, as required by the
+    /// datasheet.
+    pub fn set_filter(&mut self, filter_mode: FilterMode) -> Result<(), Error<E>> {
+        let auto = self.config & (1 << 6);
+        if auto != 0 {
+            self.modify_config(1 << 6, 0)?;
+        }
+        self.modify_config(1, filter_mode.to_bit() as u8)?;
+        if auto != 0 {
+            self.modify_config(0, auto)?;
+        }
+        Ok(())
+    }
+
+    /// Start automatic conversion with the given filter mode.
+    /// 
+    /// # Remarks
+    /// 
+    /// This enables V_BIAS and automatic conversion, preserving the sensor type. If
+    /// automatic conversion is already running, it is stopped first so the filter can
+    /// be changed safely. New conversions are then available every 16.7ms (60Hz) or
+    /// 20ms (50Hz), use `wait_for_ready` or `read_default_conversion` to read them.
+    pub fn start_continuous(&mut self, filter_mode: FilterMode) -> Result<(), Error<E>> {
+        if self.config & (1 << 6) != 0 {
+            self.modify_config(1 << 6, 0)?;
+        }
+        self.modify_config(1, (1 << 7) | filter_mode.to_bit() as u8)?;
+        self.modify_config(0, 1 << 6)?;
+        Ok(())
+    }
+
+    /// Stop automatic conversion.
+    /// 
+    /// # Remarks
+    /// 
+    /// V_BIAS is left enabled, so `read_one_shot` can be used without waiting for it
+    /// to settle. Use `power_down` to disable it as well.
+    pub fn stop_continuous(&mut self) -> Result<(), Error<E>> {
+        self.modify_config(1 << 6, 0)?;
+        Ok(())
+    }
+
+    /// Set the physical wiring of the sensor, preserving all other settings.
+    /// 
+    /// # Remarks
+    /// 
+    /// This writes the sensor type bit for the wiring and records the wiring, so it can
+    /// be reported by `wiring`. Two and four wire sensors result in the same register
+    /// write, see `Wiring`.
+    pub fn set_wiring(&mut self, wiring: Wiring) -> Result<(), Error<E>> {
+        self.wiring = wiring;
+        self.modify_config(1 << 4, (wiring.sensor_type() as u8) << 4)?;
+        Ok(())
+    }
+
+    /// The physical wiring of the sensor.
+    /// 
+    /// # Remarks
+    /// 
+    /// This is a best-effort value: the wiring set with `set_wiring` or the builder is
+    /// reported as long as it matches the configured sensor type. Otherwise, e.g. after
+    /// `configure` or `refresh_config` changed the sensor type, it is derived from the
+    /// configuration, in which case a two or four wire sensor is reported as `TwoWire`.
+    pub fn wiring(&self) -> Wiring {
+        self.wiring
+    }
+
+    /// Read back and decode the configuration register.
+    /// 
+    /// # Remarks
+    /// 
+    /// This can be used to verify that the device accepted the configuration, e.g. after
+    /// a power glitch. The cached configuration is not updated, see `refresh_config`.
+    /// The sensor type can't distinguish two and four wire sensors, see `wiring`.
+    pub fn read_config(&mut self) -> Result<Config, Error<E>> {
+        let bits = self.read(Register::CONFIG)?;
+        Ok(Config::from_bits(bits))
+    }
+
+    /// Read the configuration register without decoding it.
+    /// 
+    /// # Remarks
+    /// 
+    /// Like `read_config` this doesn't update the cached configuration. Useful to
+    /// diagnose why a configuration didn't stick, e.g. by comparing it to
+    /// `config().to_bits()`.
+    pub fn read_config_raw(&mut self) -> Result<u8, Error<E>> {
+        Ok(self.read(Register::CONFIG)?)
+    }
+
+    /// Read the configuration register and decode every bit, see `ConfigBits`.
+    /// 
+    /// # Remarks
+    /// 
+    /// Unlike `read_config` this includes the fault detection cycle (D3, D2) and fault
+    /// status clear (D1) bits. The cached configuration is not updated.
+    pub fn read_config_bits(&mut self) -> Result<ConfigBits, Error<E>> {
+        let bits = self.read_config_raw()?;
+        Ok(ConfigBits::from_bits(bits))
+    }
+
+    /// Write the configuration register without any checks.
+    /// 
+    /// # Remarks
+    /// 
+    /// Unlike `configure_with` this doesn't prevent changing the filter while automatic
+    /// conversion is enabled. The command bits (one shot, fault detection and fault
+    /// status clear) are written but not cached, see `config`.
+    pub fn write_config_raw(&mut self, bits: u8) -> Result<(), Error<E>> {
+        self.write(Register::CONFIG, bits)?;
+        self.cache_config(bits);
+        Ok(())
+    }
+
+    /// Determine whether a one shot conversion is still in progress.
+    /// 
+    /// # Remarks
+    /// 
+    /// The one shot bit of the configuration register clears itself once the
+    /// conversion is finished, so polling this is an alternative to the ready pin on
+    /// boards that don't connect it. This doesn't apply to automatic conversion.
+    pub fn conversion_in_progress(&mut self) -> Result<bool, Error<E>> {
+        Ok(self.read(Register::CONFIG)? & (1 << 5) != 0)
+    }
+
+    /// The last configuration written to the device.
+    /// 
+    /// # Remarks
+    /// 
+    /// The configuration register is cached so partial updates such as `set_filter`
+    /// or `power_down` only need a single write. This returns the cached value without
+    /// any SPI traffic.
+    /// 
+    /// The one shot bit clears itself once the conversion is finished, so it is never
+    /// cached and `one_shot` is always `false`. A later partial update therefore can't
+    /// trigger another conversion by accident.
+    pub fn config(&self) -> Config {
+        Config::from_bits(self.config)
+    }
+
+    /// Re-read the configuration register and update the cached configuration.
+    /// 
+    /// # Remarks
+    /// 
+    /// Use this if the device may have been reset or reconfigured behind the driver's
+    /// back, e.g. after a power glitch or when the driver is created for a device that
+    /// was already configured.
+    pub fn refresh_config(&mut self) -> Result<Config, Error<E>> {
+        let bits = self.read(Register::CONFIG)?;
+        self.cache_config(bits);
+        Ok(Config::from_bits(bits))
+    }
+
+    /// Check whether the configuration register still matches the cached
+    /// configuration.
+    /// 
+    /// # Remarks
+    /// 
+    /// The command bits (one shot, fault detection and fault status clear) clear
+    /// themselves and are ignored. A brown-out or a glitch on the bus can silently
+    /// change the register, e.g. disable V_BIAS or automatic conversion, so long
+    /// running applications can call this periodically and reconfigure with
+    /// `configure_with(config())` if it returns `false`. The cache is not updated.
+    pub fn verify_config(&mut self) -> Result<bool, Error<E>> {
+        let bits = self.read(Register::CONFIG)?;
+        Ok(bits & CONFIG_SETTINGS == self.config)
+    }
+
+    /// Set the resistance of the reference resistor in Ohms.
+    /// 
+    /// # Remarks
+    /// 
+    /// The default is 400 Ohms for a PT100 and 4300 Ohms for a PT1000, but many PT100
+    /// boards use 430 Ohms instead. This also updates the calibration value, which is
+    /// the same resistance multiplied by 100 and rounded for the integer conversions.
+    /// The floating point conversions such as `read_resistance` use the exact value.
+    /// 
+    /// Pass the measured value of the reference resistor (e.g. `430.2`) to correct its
+    /// tolerance. Every resistance is proportional to the reference resistance, so this
+    /// scales all readings by the same factor. In contrast, `set_calibration` and
+    /// `calibrate_at_100c` derive an effective reference resistance from a single
+    /// measurement of the element, which also absorbs any deviation of the element
+    /// itself at that temperature. The two replace each other, so only use one.
+    pub fn set_reference_resistance(&mut self, ohms: ReferenceResistance) {
+        self.params = ConversionParams::with_reference_resistance(self.params.rtd_type, ohms.0)
+            .with_overrides_of(&self.params);
+    }
+
+    /// The resistance of the reference resistor in Ohms.
+    pub fn reference_resistance(&self) -> f32 {
+        self.params.reference_ohms
+    }
+
+    /// The temperature resolution in degrees Celcius at the current settings, see
+    /// `ConversionParams::temperature_resolution_c`.
+    /// 
+    /// # Remarks
+    /// 
+    /// Averaging `n` samples with `read_averaged` only helps as long as the noise is
+    /// larger than this resolution.
+    pub fn temperature_resolution_c(&self) -> f32 {
+        self.params.temperature_resolution_c()
+    }
+
+    /// Classify the connected element by its resistance at a known temperature, see
+    /// `RtdType::classify`.
+    /// 
+    /// # Remarks
+    /// 
+    /// Useful for acceptance tests of incoming sensors, e.g. with `measure_resistance`
+    /// while the element sits in an ice bath. The resistance has to be measured with a
+    /// calibrated reference resistor, otherwise the board tolerance dominates.
+    pub fn classify_sensor(&self, measured_ohms: f32, reference_temp_c: f32) -> RtdClass {
+        self.params.rtd_type.classify(measured_ohms, reference_temp_c)
+    }
+
+    /// Set the calibration reference resistance.
+    /// This can be used to calibrate inaccuracies of both the reference resistor 
+    /// and the RTD element.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `calib` - The reference resistance, e.g. `Calibration::from_ohms(400.0)` or
+    ///             `Calibration::from_centi_ohms(40000)` for 400 Ohms
+    /// 
+    /// # Remarks
+    /// 
+    /// You can perform calibration by putting the sensor in boiling (100 degrees 
+    /// Celcius) water and then measuring the raw value using `read_raw`. Calculate 
+    /// `calib` using `compute_calibration`, or use `calibrate_at_100c` to do all of this
+    /// in one step.
+    pub fn set_calibration(&mut self, calib: Calibration) -> Result<(), Error<E>> {
+        self.params = ConversionParams::with_calibration(self.params.rtd_type, calib.0)
+            .with_overrides_of(&self.params);
+        Ok(())
+    }
+
+    /// The current calibration value, i.e. the reference resistance in Ohms multiplied
+    /// by 100.
+    pub fn calibration(&self) -> u32 {
+        self.params.calibration
+    }
+
+    /// The parameters used to convert the RTD registers.
+    /// 
+    /// # Remarks
+    /// 
+    /// These combine the RTD type, the calibration and the two-point correction, and
+    /// can be stored to convert raw values logged with `read_raw` later, see
+    /// `ConversionParams::to_temperature`.
+    pub fn conversion_params(&self) -> ConversionParams {
+        self.params
+    }
+
+    /// Replace the parameters used to convert the RTD registers, e.g. ones previously
+    /// returned by `conversion_params`.
+    pub fn set_conversion_params(&mut self, params: ConversionParams) {
+        self.params = params;
+    }
+
+    /// Replace the built-in temperature conversion with a custom one, see
+    /// `ConversionParams::with_conversion`.
+    /// 
+    /// # Remarks
+    /// 
+    /// `read_default_conversion` and the readings then route the measured resistance
+    /// through `conversion`, e.g. for a thermistor-style linearization. The custom
+    /// conversion is kept when the calibration or the RTD type is changed.
+    pub fn set_conversion(&mut self, conversion: Option<fn(f32) -> f32>) {
+        self.params = self.params.with_conversion(conversion);
+    }
+
+    /// Set a fixed temperature offset trim in degrees Celcius multiplied by 100.
+    /// 
+    /// # Remarks
+    /// 
+    /// This is meant for a pure offset found when comparing against a reference
+    /// thermometer in the field, e.g. -50 if the readings are 0.5 degrees Celcius too
+    /// high. It is applied after the conversion in `read_default_conversion` and the
+    /// readings, while `set_calibration` and `set_two_point_calibration` correct the
+    /// gain of the resistance measurement. Both compose, and the offset is kept when
+    /// the calibration changes. See `ConversionParams::with_temperature_offset`.
+    pub fn set_temperature_offset(&mut self, centi_c: i32) {
+        self.params = self.params.with_temperature_offset(centi_c);
+    }
+
+    /// The temperature offset trim in degrees Celcius multiplied by 100, see
+    /// `set_temperature_offset`.
+    pub fn temperature_offset(&self) -> i32 {
+        self.params.offset
+    }
+
+    /// Set how the integer conversion of `read_default_conversion` rounds, see
+    /// `ConversionParams::with_rounding`.
+    /// 
+    /// # Remarks
+    /// 
+    /// The default `Rounding::Truncate` matches earlier versions of the driver but
+    /// reads about half an LSB low, `Rounding::Nearest` removes that bias.
+    pub fn set_rounding(&mut self, mode: Rounding) {
+        self.params = self.params.with_rounding(mode);
+    }
+
+    /// The rounding of the integer conversion, see `set_rounding`.
+    pub fn rounding(&self) -> Rounding {
+        self.params.rounding
+    }
+
+    /// Set the coefficients correcting the nonlinearity of the ADC, or `None` to
+    /// disable the correction, see `ConversionParams::with_adc_linearization`.
+    /// 
+    /// # Remarks
+    /// 
+    /// This is off by default and only meant for precision setups chasing the last
+    /// hundredths of a degree. The correction is applied to the RTD ratio before any
+    /// other conversion and kept when the calibration changes.
+    pub fn set_adc_linearization(&mut self, coeffs: Option<[f32; 3]>) {
+        self.params = self.params.with_adc_linearization(coeffs);
+    }
+
+    /// The coefficients of the ADC nonlinearity correction, see
+    /// `set_adc_linearization`.
+    pub fn adc_linearization(&self) -> Option<[f32; 3]> {
+        self.params.linearization
+    }
+
+    /// Set the channel included in each `Reading` of this driver.
+    /// 
+    /// # Remarks
+    /// 
+    /// The channel isn't used by the driver itself, it only tags the readings of
+    /// several MAX31865 chips for logging. It defaults to `0`.
+    pub fn set_channel(&mut self, channel: u8) {
+        self.channel = channel;
+    }
+
+    /// The channel included in each `Reading`, see `set_channel`.
+    pub fn channel(&self) -> u8 {
+        self.channel
+    }
+
+    /// Set the number of conversions averaged by `read_one_shot`.
+    /// 
+    /// # Remarks
+    /// 
+    /// This keeps the averaging policy in one place instead of passing a count to
+    /// `read_averaged` at every call site. The default of 1 performs a single
+    /// conversion, and 0 is treated as 1. `read_default_conversion` can't wait for
+    /// conversions, so it always returns the latest one.
+    pub fn set_sample_count(&mut self, samples: u8) {
+        self.sample_count = samples.max(1);
+    }
+
+    /// The number of conversions averaged by `read_one_shot`, see `set_sample_count`.
+    pub fn sample_count(&self) -> u8 {
+        self.sample_count
+    }
+
+    /// Restore the default calibration for the configured RTD type.
+    /// 
+    /// # Remarks
+    /// 
+    /// This undoes `set_calibration`, `set_reference_resistance` and `calibrate_at_100c`,
+    /// see `RtdType::default_calibration` for the values, e.g. `40000` for a PT100.
+    pub fn reset_calibration(&mut self) {
+        self.params = ConversionParams::new(self.params.rtd_type)
+            .with_overrides_of(&self.params);
+    }
+
+    /// Calibrate the device assuming the sensor is currently at 100 degrees Celcius.
+    /// 
+    /// # Remarks
+    /// 
+    /// This reads the current RTD value, computes the calibration value using
+    /// `compute_calibration` (scaled for a PT1000), stores it and returns it. The
+    /// sensor must actually be at 100 degrees Celcius, e.g. in boiling water at sea
+    /// level, otherwise all further readings will be off.
+    pub fn calibrate_at_100c(&mut self) -> Result<u32, Error<E>> {
+        let ratio = self.read_rtd()?;
+        let calib = compute_calibration(ratio_to_raw(ratio))
+            * (self.params.rtd_type.nominal_resistance() / 100);
+        self.set_calibration(Calibration(calib))?;
+
+        Ok(calib)
+    }
+
+    /// Correct the measured resistance using readings at two known temperatures.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `raw_low` - The value returned by `read_raw` at the lower temperature.
+    /// * `temp_low` - The lower temperature in degrees Celcius, e.g. `0.0` in an ice bath.
+    /// * `raw_high` - The value returned by `read_raw` at the higher temperature.
+    /// * `temp_high` - The higher temperature in degrees Celcius, e.g. `100.0` in boiling
+    ///                 water.
+    /// 
+    /// # Remarks
+    /// 
+    /// Unlike the single-point `calibrate_at_100c`, which only corrects the gain, this
+    /// computes a linear gain and offset correction of the resistance, which is applied
+    /// by all conversions. The correction is relative to the current reference
+    /// resistance and is removed again by `set_calibration`, `set_reference_resistance`,
+    /// `set_rtd_type` and `reset_calibration`.
+    /// 
+    /// Returns `Error::OutOfRange` if `raw_high` isn't above `raw_low` or the readings
+    /// are too far from the expected resistances to be a plausible correction.
+    pub fn set_two_point_calibration(&mut self, raw_low: u16, temp_low: f32, raw_high: u16,
+        temp_high: f32) -> Result<(), Error<E>> {
+        self.params = self.params.with_two_point(raw_low, temp_low, raw_high, temp_high)
+            .ok_or(Error::OutOfRange)?;
+        Ok(())
+    }
+
+    /// Read the raw resistance value and then perform conversion to degrees Celcius.
+    /// 
+    /// # Remarks
+    /// 
+    /// The output value is the value in degrees Celcius multiplied by 100, and is
+    /// negative for temperatures below 0 degrees Celcius. For a PT1000 the resistance
+    /// is scaled down to the PT100 range before the lookup.
+    #[cfg(feature = "lookup-table")]
+    pub fn read_default_conversion(&mut self) -> Result<i32, Error<E>> {
+        let ratio = self.read_rtd()?;
+
+        Ok(self.params.ratio_to_temp(ratio))
+    }
+
+    /// Trigger a single conversion, wait for it to complete and return the converted
+    /// temperature.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `delay` - The delay used to wait for the conversion to finish.
+    /// 
+    /// # Remarks
+    /// 
+    /// This sets the one shot bit in the configuration register and then waits the
+    /// conversion time for the configured filter (52ms for 60Hz and 62.5ms for 50Hz)
+    /// before reading the result. Automatic conversion should be disabled for this
+    /// to work. The output value is the same as for `read_default_conversion`.
+    /// 
+    /// If V_BIAS is disabled, it is enabled first and `VBIAS_SETTLE_TIME_US` is waited
+    /// before the conversion is started. V_BIAS is left enabled afterwards.
+    /// 
+    /// If a sample count above 1 is set with `set_sample_count`, that many conversions
+    /// are averaged like `read_averaged`.
+    #[cfg(feature = "lookup-table")]
+    pub fn read_one_shot(&mut self, delay: &mut impl DelayNs) -> Result<i32, Error<E>> {
+        if self.sample_count > 1 {
+            return self.read_averaged(self.sample_count, delay);
+        }
+
+        self.single_one_shot(delay)
+    }
+
+    // a single one shot conversion, regardless of the sample count
+    #[cfg(feature = "lookup-table")]
+    fn single_one_shot(&mut self, delay: &mut impl DelayNs) -> Result<i32, Error<E>> {
+        if self.config & (1 << 7) == 0 {
+            self.modify_config(0, 1 << 7)?;
+            delay.delay_us(VBIAS_SETTLE_TIME_US);
+        }
+
+        let conf = self.modify_config(0, 1 << 5)?;
+
+        delay.delay_us(Config::from_bits(conf).filter_mode.conversion_time_us());
+
+        self.read_default_conversion()
+    }
+
+    /// Perform several one shot conversions and return the average temperature.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `samples` - The number of conversions to perform.
+    /// * `delay` - The delay used to wait for each conversion to finish.
+    /// 
+    /// # Remarks
+    /// 
+    /// Samples which report a fault are discarded and the faults are cleared before
+    /// the next conversion. If every sample reports a fault, the last fault is returned
+    /// as `Error::Fault`. Returns `Error::OutOfRange` if `samples` is 0. The output value
+    /// is the same as for `read_default_conversion`.
+    #[cfg(feature = "lookup-table")]
+    pub fn read_averaged(&mut self, samples: u8, delay: &mut impl DelayNs) -> Result<i32, Error<E>> {
+        let mut sum = 0;
+        let mut count = 0;
+        let mut fault = None;
+
+        for _ in 0..samples {
+            match self.single_one_shot(delay) {
+                Ok(temp) => {
+                    sum += temp;
+                    count += 1;
+                }
+                Err(Error::Fault(status)) => {
+                    fault = Some(status);
+                    self.clear_faults()?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        match (count, fault) {
+            (0, Some(status)) => Err(Error::Fault(status)),
+            (0, None) => Err(Error::OutOfRange),
+            _ => Ok(sum / count),
+        }
+    }
+
+    /// Fill a buffer with consecutive one shot conversions.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `buf` - The buffer receiving the temperatures, in the same format as
+    ///           `read_default_conversion`.
+    /// * `delay` - The delay used to wait for each conversion to finish.
+    /// 
+    /// # Remarks
+    /// 
+    /// Returns the number of samples written, which is `buf.len()` unless a
+    /// conversion reports a fault. In that case filling stops early so the samples
+    /// before the fault can still be used, and the fault status can be read with
+    /// `read_fault_status`. If the very first conversion faults, `Error::Fault` is
+    /// returned instead. The sample count of `set_sample_count` is ignored.
+    #[cfg(feature = "lookup-table")]
+    pub fn fill(&mut self, buf: &mut [i32], delay: &mut impl DelayNs) -> Result<usize, Error<E>> {
+        for (i, sample) in buf.iter_mut().enumerate() {
+            match self.single_one_shot(delay) {
+                Ok(temp) => *sample = temp,
+                Err(Error::Fault(_)) if i > 0 => return Ok(i),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(buf.len())
+    }
+
+    /// Perform a complete measurement and return the temperature in degrees Celcius.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `delay` - The delay used to wait for V_BIAS to settle and the conversion to
+    ///             finish.
+    /// 
+    /// # Remarks
+    /// 
+    /// This disables automatic conversion, enables V_BIAS and waits
+    /// `VBIAS_SETTLE_TIME_US`, runs a one shot conversion, reads the result and
+    /// disables V_BIAS again, so the element isn't heated between measurements. A set
+    /// fault bit is returned as `Error::Fault` like `read_rtd`, V_BIAS is disabled in
+    /// that case too. The conversion is the same as for `Reading::temperature_c`.
+    pub fn measure(&mut self, delay: &mut impl DelayNs) -> Result<f32, Error<E>> {
+        let ratio = self.measure_ratio(delay)?;
+
+        Ok(self.params.reading(ratio_to_raw(ratio), self.channel).temperature_c)
+    }
+
+    /// Perform a complete measurement like `measure` and return only the resistance of
+    /// the RTD element in Ohms.
+    /// 
+    /// # Remarks
+    /// 
+    /// This uses the same math as `read_resistance` and never touches the temperature
+    /// conversions, so it can be used with PT500 or custom elements the built-in
+    /// conversions don't cover, together with an application specific resistance
+    /// table.
+    pub fn measure_resistance(&mut self, delay: &mut impl DelayNs) -> Result<f32, Error<E>> {
+        let ratio = self.measure_ratio(delay)?;
+
+        Ok(self.params.ratio_to_resistance(ratio))
+    }
+
+    /// Estimate the resistance of the leads of a three wire sensor.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `delay` - The delay used for both measurements.
+    /// 
+    /// # Remarks
+    /// 
+    /// This performs one measurement like `measure_resistance` with three wire
+    /// compensation and one without, and returns the difference in Ohms, which
+    /// approximates the resistance of the two compensated leads. A rising value over
+    /// time indicates a corroding connector long before the temperature is off. The
+    /// sensor type and the recorded wiring are restored afterwards, also if a
+    /// measurement fails. Like `measure`, automatic conversion and V_BIAS are disabled
+    /// afterwards.
+    pub fn measure_lead_resistance(&mut self, delay: &mut impl DelayNs) -> Result<f32, Error<E>> {
+        let (sensor_type, wiring) = (self.config & (1 << 4), self.wiring);
+
+        let lead = match self.modify_config(0, 1 << 4).map_err(Error::Spi)
+            .and_then(|_| self.measure_resistance(delay))
+        {
+            Ok(compensated) => self.modify_config(1 << 4, 0).map_err(Error::Spi)
+                .and_then(|_| self.measure_resistance(delay))
+                .map(|uncompensated| uncompensated - compensated),
+            Err(e) => Err(e),
+        };
+        let restored = self.modify_config(1 << 4, sensor_type);
+        self.wiring = wiring;
+        let lead = lead?;
+        restored?;
+
+        Ok(lead)
+    }
+
+    /// Run the fault detection cycle and, only if it found no fault, perform a complete
+    /// measurement and return the temperature.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `delay` - The delay used for the fault detection cycle and the measurement.
+    /// 
+    /// # Remarks
+    /// 
+    /// This combines `run_fault_detection_auto` and `measure` for safety relevant
+    /// measurements: any detected fault is returned as `Error::Fault` without starting
+    /// a conversion, and a set fault bit of the conversion itself is returned as
+    /// `Error::Fault` as well. The conversion is only started after V_BIAS had
+    /// `VBIAS_SETTLE_TIME_US` to settle, so there is no stale first reading to discard.
+    /// The output value is the same as for `read_default_conversion`, and V_BIAS is
+    /// disabled afterwards.
+    #[cfg(feature = "lookup-table")]
+    pub fn measure_verified(&mut self, delay: &mut impl DelayNs) -> Result<i32, Error<E>> {
+        let status = self.run_fault_detection_auto(delay)?;
+        if !status.is_ok() {
+            return Err(Error::Fault(status));
+        }
+
+        let ratio = self.measure_ratio(delay)?;
+        Ok(self.params.ratio_to_temp(ratio))
+    }
+
+    /// Perform a complete measurement like `measure` while also controlling an
+    /// external bias switch.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `delay` - The delay used to wait for the bias to settle and the conversion to
+    ///             finish.
+    /// * `internal_vbias` - Whether V_BIAS of the device is enabled for the
+    ///                      measurement as well. If `false` the V_BIAS bit is left as
+    ///                      it is.
+    /// * `bias` - Called with `true` right before the settling time and with `false`
+    ///            once the result was read, e.g. to drive a GPIO which switches the bias
+    ///            to one of several RTDs.
+    /// 
+    /// # Remarks
+    /// 
+    /// The sequence and timing is the same as for `measure`, the external switch gets
+    /// `VBIAS_SETTLE_TIME_US` to settle. It is switched off again if the conversion
+    /// fails, but not if the initial configuration write fails, as it hasn't been
+    /// switched on then.
+    pub fn measure_with_bias(&mut self, delay: &mut impl DelayNs, internal_vbias: bool,
+        bias: &mut impl FnMut(bool)) -> Result<f32, Error<E>>
+    {
+        let ratio = self.measure_ratio_with(delay, internal_vbias, bias)?;
+
+        Ok(self.params.reading(ratio_to_raw(ratio), self.channel).temperature_c)
+    }
+
+    // the measurement cycle of `measure`, returning the 15 bit ratio
+    fn measure_ratio(&mut self, delay: &mut impl DelayNs) -> Result<u16, Error<E>> {
+        self.measure_ratio_with(delay, true, &mut |_| {})
+    }
+
+    // the measurement cycle of `measure_with_bias`, returning the 15 bit ratio
+    fn measure_ratio_with(&mut self, delay: &mut impl DelayNs, internal_vbias: bool,
+        bias: &mut impl FnMut(bool)) -> Result<u16, Error<E>>
+    {
+        self.modify_config(1 << 6, (internal_vbias as u8) << 7)?;
+        bias(true);
+        delay.delay_us(VBIAS_SETTLE_TIME_US);
+
+        let ratio = match self.modify_config(0, 1 << 5) {
+            Ok(conf) => {
+                delay.delay_us(Config::from_bits(conf).filter_mode.conversion_time_us());
+                self.read_rtd()
+            }
+            Err(e) => Err(Error::Spi(e)),
+        };
+        bias(false);
+        let vbias_off = if internal_vbias {
+            self.modify_config(1 << 7, 0).map(|_| ())
+        } else {
+            Ok(())
+        };
+        let ratio = ratio?;
+        vbias_off?;
+
+        Ok(ratio)
+    }
+
+    /// Read the temperature in degrees Fahrenheit multiplied by 100.
+    /// 
+    /// # Remarks
+    /// 
+    /// This is the integer counterpart of `read_temperature_f` for MCUs without an FPU,
+    /// computed as `c * 9 / 5 + 3200` from the value of `read_default_conversion`,
+    /// e.g. 10000 (100 degrees Celcius) returns 21200.
+    #[cfg(feature = "lookup-table")]
+    pub fn read_default_conversion_f(&mut self) -> Result<i32, Error<E>> {
+        let temp = self.read_default_conversion()?;
+        Ok(temp * 9 / 5 + 3200)
+    }
+
+    /// Read the temperature in degrees Fahrenheit.
+    /// 
+    /// # Remarks
+    /// 
+    /// This uses the same conversion as `read_default_conversion`. The Fahrenheit value
+    /// is computed from the Celcius value multiplied by 100, so no extra rounding is
+    /// introduced beyond that of the lookup table.
+    #[cfg(feature = "lookup-table")]
+    pub fn read_temperature_f(&mut self) -> Result<f32, Error<E>> {
+        let temp = self.read_default_conversion()?;
+        Ok(temp as f32 * 9.0 / 500.0 + 32.0)
+    }
+
+    /// Read the temperature in Kelvin.
+    /// 
+    /// # Remarks
+    /// 
+    /// This uses the same conversion as `read_default_conversion`, computed from the
+    /// Celcius value multiplied by 100.
+    #[cfg(feature = "lookup-table")]
+    pub fn read_temperature_k(&mut self) -> Result<f32, Error<E>> {
+        let temp = self.read_default_conversion()?;
+        Ok(temp as f32 / 100.0 + 273.15)
+    }
+
+    /// Read the raw resistance value and convert it to degrees Celcius using the
+    /// Callendar-Van Dusen equation.
+    /// 
+    /// # Remarks
+    /// 
+    /// Unlike `read_default_conversion` this does not use the lookup table and
+    /// returns a continuous value. The temperature offset and a custom conversion are
+    /// applied, see `ConversionParams::to_temperature_c`. Requires the `float` feature.
+    #[cfg(feature = "float")]
+    pub fn read_temperature_c(&mut self) -> Result<f32, Error<E>> {
+        let ratio = self.read_rtd()?;
+
+        Ok(self.params.to_temperature_c(ratio_to_raw(ratio)))
+    }
+
+    /// Read the raw value, resistance and temperature of a single conversion.
+    /// 
+    /// # Remarks
+    /// 
+    /// All values are derived from one read of the RTD registers, so they always
+    /// belong to the same conversion. Unlike `read_rtd` a set fault bit is not
+    /// returned as an error, but reported through `Reading::fault`, in which case
+    /// the fault status can be read with `read_fault_status`.
+    pub fn read_all(&mut self) -> Result<Reading, Error<E>> {
+        let raw = self.read_raw()?;
+
+        Ok(self.params.reading(raw, self.channel))
+    }
+
+    /// Read a single conversion like `read_all` and stamp it with the given time.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `now` - The current value of a monotonic clock, in any unit, e.g. the
+    ///           milliseconds since boot from a hardware timer.
+    /// 
+    /// # Remarks
+    /// 
+    /// The time is stored in `Reading::timestamp`, so it can't get out of step with the
+    /// sample when readings are queued for logging. Capture `now` right before the call.
+    pub fn read_all_at(&mut self, now: u64) -> Result<Reading, Error<E>> {
+        Ok(Reading {
+            timestamp: Some(now),
+            ..self.read_all()?
+        })
+    }
+
+    /// Read the temperature of a single conversion together with its fault status.
+    /// 
+    /// # Remarks
+    /// 
+    /// A sensor fault is never returned as an error, only bus errors are. If the fault
+    /// bit is set, the fault status register is read as well and reported through
+    /// `Sample::fault` next to the (probably clamped) temperature, so both can be
+    /// logged together. The faults stay latched, see `clear_faults`. The temperature is
+    /// the same as for `read_default_conversion`.
+    pub fn read_sample(&mut self) -> Result<Sample, Error<E>> {
+        let raw = self.read_raw()?;
+        let fault_bits = if raw & 1 != 0 { self.read(Register::FAULT_STATUS)? } else { 0 };
+
+        Ok(self.params.to_sample(raw, fault_bits))
+    }
+
+    /// Read the temperature in degrees Celcius multiplied by 100 as an `i16`.
+    /// 
+    /// # Remarks
+    /// 
+    /// This halves the storage of `read_default_conversion` for compact logging, but
+    /// only covers -327.68 to 327.67 degrees Celcius, above that `Error::OutOfRange`
+    /// is returned. Use `read_temperature_deci_i16` for the full range of the element.
+    /// The conversion is the same as for `Reading::temperature_c`, and a set fault bit
+    /// is returned as `Error::Fault` like `read_rtd`.
+    pub fn read_temperature_centi_i16(&mut self) -> Result<i16, Error<E>> {
+        let ratio = self.read_rtd()?;
+        let reading = self.params.reading(ratio_to_raw(ratio), self.channel);
+
+        celsius_to_i16(reading.temperature_c, 100.0).ok_or(Error::OutOfRange)
+    }
+
+    /// Read the temperature in degrees Celcius multiplied by 10 as an `i16`.
+    /// 
+    /// # Remarks
+    /// 
+    /// Trades a tenth of the resolution of `read_temperature_centi_i16` for a range
+    /// of -3276.8 to 3276.7 degrees Celcius, which covers every platinum RTD.
+    pub fn read_temperature_deci_i16(&mut self) -> Result<i16, Error<E>> {
+        let ratio = self.read_rtd()?;
+        let reading = self.params.reading(ratio_to_raw(ratio), self.channel);
+
+        celsius_to_i16(reading.temperature_c, 10.0).ok_or(Error::OutOfRange)
+    }
+
+    /// Read the temperature in degrees Celcius as a fixed-point value.
+    /// 
+    /// # Remarks
+    /// 
+    /// This uses the same integer conversion as `read_default_conversion`, but returns
+    /// the fractional temperature without requiring floating point support. Requires
+    /// the `fixed` feature.
+    #[cfg(all(feature = "lookup-table", feature = "fixed"))]
+    pub fn read_temperature_fixed(&mut self) -> Result<fixed::types::I16F16, Error<E>> {
+        let temp = self.read_default_conversion()?;
+
+        Ok(centi_to_fixed(temp))
+    }
+
+    /// Read the temperature as a `uom` quantity.
+    /// 
+    /// # Remarks
+    /// 
+    /// This uses the same conversion as `Reading::temperature_c`, and fails with
+    /// `Error::Fault` if the fault bit is set like `read_rtd`. Requires the `uom`
+    /// feature.
+    #[cfg(feature = "uom")]
+    pub fn read_thermodynamic_temperature(&mut self)
+        -> Result<uom::si::f32::ThermodynamicTemperature, Error<E>> {
+        let ratio = self.read_rtd()?;
+        let reading = self.params.reading(ratio_to_raw(ratio), self.channel);
+
+        Ok(celsius_to_uom(reading.temperature_c))
+    }
+
+    /// Read the resistance of the RTD element in Ohms.
+    /// 
+    /// # Remarks
+    /// 
+    /// The resistance is calculated as `ratio / 32768 * reference_resistance`, where the
+    /// reference resistance is set by `set_reference_resistance` or `set_calibration`.
+    /// No temperature conversion is involved, so this works for any element, e.g. a
+    /// PT500 or a custom platinum RTD.
+    pub fn read_resistance(&mut self) -> Result<f32, Error<E>> {
+        let ratio = self.read_rtd()?;
+
+        Ok(self.params.ratio_to_resistance(ratio))
+    }
+
+    /// Read the raw RTD value.
+    /// 
+    /// # Remarks
+    /// 
+    /// The raw value is the value of the combined MSB and LSB registers.
+    /// The first 15 bits specify the ohmic value in relation to the reference
+    /// resistor (i.e. 2^15 - 1 would be the exact same resistance as the reference
+    /// resistor). See manual for further information.
+    /// The last bit specifies if the conversion was successful. 
+    /// 
+    /// Both registers are read in a single transaction using the address
+    /// auto-increment, so the bytes always belong to the same conversion.
+    /// 
+    /// The value is returned unmasked, use `read_rtd_reading` to get the ratio and
+    /// the fault bit decoded. Reading the registers returns the ready pin to high,
+    /// use `peek_raw` to look at the last value again without another read.
+    pub fn read_raw(&mut self) -> Result<u16, Error<E>> {
+        let buffer: [u8; 3] = self.read_many(Register::RTD_MSB)?;
+        let raw = ((buffer[1] as u16) << 8) | buffer[2] as u16;
+        self.last_raw = Some(raw);
+        self.one_shot_pending = false;
+
+        Ok(raw)
+    }
+
+    /// The value returned by the last read of the RTD registers, without accessing the
+    /// device.
+    /// 
+    /// # Remarks
+    /// 
+    /// The MAX31865 always returns the ready pin to high when the RTD registers are
+    /// read, there is no way to read them without clearing the ready state. Event
+    /// driven designs can instead read the registers once per falling edge of the
+    /// ready pin, e.g. with `read_raw` or `read_all`, and peek at the value here as
+    /// often as needed. Returns `None` until the registers were read for the first
+    /// time.
+    pub fn peek_raw(&self) -> Option<u16> {
+        self.last_raw
+    }
+
+    /// Read the RTD registers and decode the ratio and the fault bit.
+    /// 
+    /// # Remarks
+    /// 
+    /// Unlike `read_rtd` a set fault bit is not returned as an error.
+    pub fn read_rtd_reading(&mut self) -> Result<RtdReading, Error<E>> {
+        let raw = self.read_raw()?;
+        Ok(RtdReading::from_raw(raw))
+    }
+
+    /// Read the RTD registers and the fault status register together.
+    /// 
+    /// # Remarks
+    /// 
+    /// Returns the raw value as `read_raw` does and the undecoded fault status bits,
+    /// see `FaultStatus::from_bits`. The registers aren't adjacent, so a single burst
+    /// from the RTD MSB register (0x01) through the fault status register (0x07) is
+    /// read and the threshold registers in between are discarded. That is one SPI
+    /// transaction of 8 bytes instead of two with 6 bytes in total, which guarantees
+    /// the fault bits belong to the same conversion as the reading.
+    pub fn read_measurement_burst(&mut self) -> Result<(u16, u8), Error<E>> {
+        let buffer: [u8; 8] = self.read_many(Register::RTD_MSB)?;
+        let raw = ((buffer[1] as u16) << 8) | buffer[2] as u16;
+        self.last_raw = Some(raw);
+        self.one_shot_pending = false;
+
+        Ok((raw, buffer[7]))
+    }
+
+    /// Read the 15 bit RTD ratio, checking the fault bit.
+    /// 
+    /// # Remarks
+    /// 
+    /// This is the value of `read_raw` with the fault bit removed. If the fault bit is
+    /// set, the fault status register is read and returned as `Error::Fault` instead.
+    pub fn read_rtd(&mut self) -> Result<u16, Error<E>> {
+        let raw = self.read_raw()?;
+        if raw & 1 != 0 {
+            return Err(Error::Fault(self.read_fault_status()?));
+        }
+
+        Ok(raw_to_ratio(raw))
+    }
+
+    /// Set the high fault threshold.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `ratio` - The 15 bit threshold value in relation to the reference resistor,
+    ///             in the same format as the ratio returned by `read_raw`.
+    /// 
+    /// # Remarks
+    /// 
+    /// The fault status register will flag an RTD high threshold fault whenever
+    /// the measured ratio is above this value.
+    pub fn set_high_fault_threshold(&mut self, ratio: u16) -> Result<(), Error<E>> {
+        self.write_threshold(Register::HIGH_FAULT_THRESHOLD_MSB, ratio)?;
+        Ok(())
+    }
+
+    /// Set the low fault threshold.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `ratio` - The 15 bit threshold value in relation to the reference resistor,
+    ///             in the same format as the ratio returned by `read_raw`.
+    /// 
+    /// # Remarks
+    /// 
+    /// The fault status register will flag an RTD low threshold fault whenever
+    /// the measured ratio is below this value.
+    pub fn set_low_fault_threshold(&mut self, ratio: u16) -> Result<(), Error<E>> {
+        self.write_threshold(Register::LOW_FAULT_THRESHOLD_MSB, ratio)?;
+        Ok(())
+    }
+
+    /// Set the high fault threshold to a temperature.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `celsius` - The temperature in degrees Celcius above which a fault should be flagged.
+    /// 
+    /// # Remarks
+    /// 
+    /// The temperature is converted to a resistance using the lookup table and then to a
+    /// ratio using the current calibration value. The ratio is rounded to the nearest
+    /// value. Returns `Error::OutOfRange` without writing the threshold if the temperature
+    /// is outside of `temp_conversion::temperature_range` or the resistance exceeds the
+    /// reference resistance.
+    pub fn set_high_fault_threshold_temp(&mut self, celsius: f32) -> Result<(), Error<E>> {
+        let ratio = self.params.temp_to_ratio(celsius)
+            .ok_or(Error::OutOfRange)?;
+        self.set_high_fault_threshold(ratio)
+    }
+
+    /// Set the low fault threshold to a temperature.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `celsius` - The temperature in degrees Celcius below which a fault should be flagged.
+    /// 
+    /// # Remarks
+    /// 
+    /// The temperature is converted to a resistance using the lookup table and then to a
+    /// ratio using the current calibration value. The ratio is rounded to the nearest
+    /// value. Returns `Error::OutOfRange` without writing the threshold if the temperature
+    /// is outside of `temp_conversion::temperature_range` or the resistance exceeds the
+    /// reference resistance.
+    pub fn set_low_fault_threshold_temp(&mut self, celsius: f32) -> Result<(), Error<E>> {
+        let ratio = self.params.temp_to_ratio(celsius)
+            .ok_or(Error::OutOfRange)?;
+        self.set_low_fault_threshold(ratio)
+    }
+
+    /// Read the 15 bit high fault threshold value.
+    /// 
+    /// # Remarks
+    /// 
+    /// The value is in the same format as passed to `set_high_fault_threshold`, with
+    /// the unused D0 bit dropped. Use this to verify the threshold after a reset.
+    pub fn read_high_fault_threshold(&mut self) -> Result<u16, Error<E>> {
+        Ok(self.read_threshold(Register::HIGH_FAULT_THRESHOLD_MSB)?)
+    }
+
+    /// Read the 15 bit low fault threshold value.
+    /// 
+    /// # Remarks
+    /// 
+    /// The value is in the same format as passed to `set_low_fault_threshold`, with
+    /// the unused D0 bit dropped. Use this to verify the threshold after a reset.
+    pub fn read_low_fault_threshold(&mut self) -> Result<u16, Error<E>> {
+        Ok(self.read_threshold(Register::LOW_FAULT_THRESHOLD_MSB)?)
+    }
+
+    /// Program both fault thresholds from temperatures for alert driven designs.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `low_c` - The temperature in degrees Celcius below which an alert is raised.
+    /// * `high_c` - The temperature in degrees Celcius above which an alert is raised.
+    /// 
+    /// # Remarks
+    /// 
+    /// The MAX31865 has no separate fault pin, but every conversion is compared to the
+    /// thresholds and sets the fault bit of the RTD registers on an excursion. In
+    /// automatic conversion mode, let the falling edge of the ready pin (see
+    /// `ready_pin`) wake the application, read the conversion with `read_rtd_reading`,
+    /// and only if its fault bit is set check `read_alert_state`, followed by
+    /// `clear_faults`.
+    /// 
+    /// Returns `Error::OutOfRange` without writing anything if `low_c` isn't below
+    /// `high_c` or either temperature can't be converted, see
+    /// `set_high_fault_threshold_temp`.
+    pub fn configure_alert(&mut self, low_c: f32, high_c: f32) -> Result<(), Error<E>> {
+        let ratios = (self.params.temp_to_ratio(low_c), self.params.temp_to_ratio(high_c));
+        let (low, high) = match ratios {
+            (Some(low), Some(high)) if low < high => (low, high),
+            _ => return Err(Error::OutOfRange),
+        };
+
+        self.set_low_fault_threshold(low)?;
+        self.set_high_fault_threshold(high)
+    }
+
+    /// Program both fault thresholds from temperatures in degrees Celcius.
+    /// 
+    /// # Remarks
+    /// 
+    /// The same as `configure_alert`, including the errors.
+    pub fn set_fault_thresholds_celsius(&mut self, low_c: f32, high_c: f32)
+        -> Result<(), Error<E>>
+    {
+        self.configure_alert(low_c, high_c)
+    }
+
+    /// Read which of the fault thresholds was crossed.
+    /// 
+    /// # Remarks
+    /// 
+    /// The threshold faults are latched, so this reports any excursion since the last
+    /// `clear_faults`, see `configure_alert`.
+    pub fn read_alert_state(&mut self) -> Result<AlertState, Error<E>> {
+        let faults = self.read_fault_status()?;
+        Ok(AlertState {
+            high: faults.rtd_high_threshold,
+            low: faults.rtd_low_threshold,
+        })
+    }
+
+    /// Quickly check whether the RTD element appears to be connected.
+    /// 
+    /// # Remarks
+    /// 
+    /// This reads the RTD registers and the fault status register and returns `false`
+    /// if the ratio is pinned at either rail, as for an open or shorted element, or if
+    /// one of the REFIN-, RTDIN- or over/undervoltage faults is set. The threshold
+    /// faults are not taken into account, as they also signal a valid temperature
+    /// outside the configured limits.
+    /// 
+    /// This is lighter than `run_fault_detection` and can be used when polling
+    /// frequently, but doesn't detect all wiring faults.
+    pub fn is_sensor_connected(&mut self) -> Result<bool, Error<E>> {
+        let ratio = raw_to_ratio(self.read_raw()?);
+        let faults = self.read_fault_status()?;
+
+        Ok(sensor_connected(ratio, faults))
+    }
+
+    /// Verify that the device is connected and SPI communication works.
+    /// 
+    /// # Remarks
+    /// 
+    /// This writes alternating bit patterns to the high fault threshold, reads them back
+    /// and restores the original value afterwards, so the threshold registers are
+    /// briefly perturbed. Returns `Error::NoConnection` if a value doesn't read back,
+    /// e.g. because MISO and MOSI are swapped or the wrong SPI mode is used. An SPI mode
+    /// 0 bus typically shifts the read values by one bit, which the patterns detect.
+    pub fn check_connection(&mut self) -> Result<(), Error<E>> {
+        let original = self.read_threshold(Register::HIGH_FAULT_THRESHOLD_MSB)?;
+        let mut connected = true;
+        for &test in CONNECTION_PATTERNS.iter().chain(core::iter::once(&original)) {
+            self.write_threshold(Register::HIGH_FAULT_THRESHOLD_MSB, test)?;
+            connected &= self.read_threshold(Register::HIGH_FAULT_THRESHOLD_MSB)? == test;
+        }
+
+        if !connected {
+            return Err(Error::NoConnection);
+        }
+        Ok(())
+    }
+
+    /// Determine whether a working MAX31865 is on the bus.
+    /// 
+    /// # Remarks
+    /// 
+    /// This is `check_connection` returning `false` instead of `Error::NoConnection`
+    /// if the fault threshold doesn't read back, for a quick check during setup. Bus
+    /// errors are still returned.
+    pub fn detect(&mut self) -> Result<bool, Error<E>> {
+        match self.check_connection() {
+            Ok(()) => Ok(true),
+            Err(Error::NoConnection) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Read and decode the fault status register.
+    /// 
+    /// # Remarks
+    /// 
+    /// Faults are latched by the MAX31865 and remain set until they are
+    /// cleared through the configuration register.
+    pub fn read_fault_status(&mut self) -> Result<FaultStatus, Error<E>> {
+        let bits = self.read(Register::FAULT_STATUS)?;
+        Ok(FaultStatus::from_bits(bits))
+    }
+
+    /// Clear any latched faults in the fault status register.
+    /// 
+    /// # Remarks
+    /// 
+    /// This writes the cached configuration with the fault status clear bit set, so
+    /// the V_BIAS, conversion mode, sensor type and filter settings are preserved.
+    pub fn clear_faults(&mut self) -> Result<(), Error<E>> {
+        self.modify_config(0, 1 << 1)?;
+        Ok(())
+    }
+
+    /// Run the automatic fault detection cycle and return the detected faults.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `delay` - The delay used while waiting for the cycle to finish.
+    /// 
+    /// # Remarks
+    /// 
+    /// This checks the RTD wiring for open or shorted connections, as recommended by
+    /// the datasheet before trusting any readings. Latched faults are cleared first and
+    /// V_BIAS is enabled (and given `VBIAS_SETTLE_TIME_US` to settle) if necessary.
+    /// Automatic conversion is paused during the cycle and restored afterwards.
+    /// 
+    /// The cycle runs with the configured sensor type, which is kept in every write to
+    /// the configuration register. The fault bits are the results of the comparators
+    /// and are decoded the same way for two, three and four wire sensors, but which
+    /// lead an open or short points at depends on the wiring, see the fault detection
+    /// section of the datasheet. Make sure the configuration matches the physical
+    /// wiring first (see `set_wiring`): a three wire sensor configured as two or four
+    /// wire, or the other way round, can report faults for a sensor that is fine.
+    /// 
+    /// Returns `Error::Timeout` if the device doesn't report the cycle as finished.
+    /// This is the same as `run_fault_detection_auto`.
+    pub fn run_fault_detection(&mut self, delay: &mut impl DelayNs) -> Result<FaultStatus, Error<E>> {
+        self.run_fault_detection_auto(delay)
+    }
+
+    /// Run the fault detection cycle with the automatic delay of the device.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `delay` - The delay used while waiting for the cycle to finish.
+    /// 
+    /// # Remarks
+    /// 
+    /// This writes 0b01 to D3:D2 of the configuration register, the device then waits
+    /// about 100us for the input filter to settle on its own. That is enough for the
+    /// filter capacitor recommended by the datasheet, use `run_fault_detection_manual`
+    /// for boards with a larger one.
+    pub fn run_fault_detection_auto(&mut self, delay: &mut impl DelayNs)
+        -> Result<FaultStatus, Error<E>>
+    {
+        self.fault_detection_cycle(delay, None)
+    }
+
+    /// Run the fault detection cycle with a delay controlled by the driver.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `delay` - The delay used for the settling time and while waiting for the cycle
+    ///             to finish.
+    /// * `settle_us` - The time the input filter needs to settle in microseconds, at
+    ///                 least five time constants of the external RC filter.
+    /// 
+    /// # Remarks
+    /// 
+    /// This follows the manual procedure of the datasheet: 0b10 is written to D3:D2 of
+    /// the configuration register, after `settle_us` 0b11, and after another
+    /// `settle_us` the cycle finishes. Otherwise this behaves like `run_fault_detection`.
+    pub fn run_fault_detection_manual(&mut self, delay: &mut impl DelayNs, settle_us: u32)
+        -> Result<FaultStatus, Error<E>>
+    {
+        self.fault_detection_cycle(delay, Some(settle_us))
+    }
+    /// Put the device into a low power state by disabling V_BIAS and automatic
+    /// conversion.
+    /// 
+    /// # Remarks
+    /// 
+    /// The sensor type and filter settings are preserved. Use `power_up` to enable
+    /// V_BIAS again before the next conversion.
+    pub fn power_down(&mut self) -> Result<(), Error<E>> {
+        self.modify_config((1 << 7) | (1 << 6), 0)?;
+        Ok(())
+    }
+
+    /// Enable V_BIAS again after a call to `power_down`.
+    /// 
+    /// # Remarks
+    /// 
+    /// Automatic conversion is not re-enabled, either use `read_one_shot` or enable it
+    /// through `configure`. Wait at least `VBIAS_SETTLE_TIME_US` before starting a
+    /// conversion, otherwise the reading will be too low.
+    pub fn power_up(&mut self) -> Result<(), Error<E>> {
+        self.set_vbias(true)
+    }
+
+    /// Enable or disable only V_BIAS.
+    /// 
+    /// # Remarks
+    /// 
+    /// Every other setting, including automatic conversion, is kept as cached, so this
+    /// is a single register write. Use it to gate V_BIAS around manual conversions to
+    /// reduce self-heating and power, and wait `VBIAS_SETTLE_TIME_US` after enabling
+    /// it before starting a conversion.
+    pub fn set_vbias(&mut self, on: bool) -> Result<(), Error<E>> {
+        self.modify_config(1 << 7, (on as u8) << 7)?;
+        Ok(())
+    }
+
+    /// Read a register directly.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `addr` - The register address (0x00 to 0x07), without the write bit.
+    /// 
+    /// # Remarks
+    /// 
+    /// This is a low-level escape hatch for configurations the driver doesn't cover.
+    /// You are on your own: the value is not decoded or validated in any way.
+    pub fn read_register(&mut self, addr: u8) -> Result<u8, Error<E>> {
+        let mut buffer = [(addr & !W) | R, 0];
+        self.spi.transfer_in_place(&mut buffer)?;
+        Ok(buffer[1])
+    }
+
+    /// Read all registers (0x00 to 0x07) in a single transaction.
+    /// 
+    /// # Remarks
+    /// 
+    /// The values are returned undecoded and indexed by their address, e.g. to include
+    /// the complete state of the device in a bug report. Nothing is written, but like
+    /// any read of the RTD registers this returns the ready pin to high.
+    pub fn read_all_registers(&mut self) -> Result<[u8; 8], Error<E>> {
+        let buffer: [u8; 9] = self.read_many(Register::CONFIG)?;
+        self.last_raw = Some(((buffer[2] as u16) << 8) | buffer[3] as u16);
+        self.one_shot_pending = false;
+
+        let mut registers = [0; 8];
+        registers.copy_from_slice(&buffer[1..]);
+        Ok(registers)
+    }
+
+    /// Write a register directly.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `addr` - The register address (0x00 to 0x07), without the write bit.
+    /// * `val` - The value to write.
+    /// 
+    /// # Remarks
+    /// 
+    /// This is a low-level escape hatch for configurations the driver doesn't cover.
+    /// You are on your own: the value is written as is, and a write to the
+    /// configuration register bypasses the cached configuration, so call
+    /// `refresh_config` afterwards to keep the driver in sync.
+    pub fn write_register(&mut self, addr: u8, val: u8) -> Result<(), Error<E>> {
+        self.spi.write(&[addr | W, val])?;
+        Ok(())
+    }
+
+    /// Read the configuration, the fault thresholds and the calibration of the device.
+    /// 
+    /// # Remarks
+    /// 
+    /// The configuration register is read back from the device, which also refreshes
+    /// the cached configuration like `refresh_config`. With the `serde` feature the
+    /// state can be stored, e.g. in flash, and reapplied with `restore` after a reset.
+    /// A two-point correction is not part of the state.
+    pub fn snapshot(&mut self) -> Result<DeviceState, Error<E>> {
+        Ok(DeviceState {
+            config: self.refresh_config()?,
+            wiring: self.wiring,
+            high_fault_threshold: self.read_high_fault_threshold()?,
+            low_fault_threshold: self.read_low_fault_threshold()?,
+            rtd_type: self.params.rtd_type,
+            calibration: self.params.calibration,
+        })
+    }
+
+    /// Write back a state previously read with `snapshot`.
+    /// 
+    /// # Remarks
+    /// 
+    /// Automatic conversion is stopped before the thresholds are written, so the
+    /// filter setting can be restored as well, and the configuration is written last.
+    /// The one shot bit of the stored configuration is ignored, as is the RTD type if
+    /// it is fixed in the type of the driver, see `Max31865Pt100`.
+    pub fn restore(&mut self, state: &DeviceState) -> Result<(), Error<E>> {
+        self.stop_continuous()?;
+        self.set_high_fault_threshold(state.high_fault_threshold)?;
+        self.set_low_fault_threshold(state.low_fault_threshold)?;
+        // a driver with a fixed RTD type keeps it, see `Max31865Pt100`
+        let rtd_type = if R0 == 0 { state.rtd_type } else { self.params.rtd_type };
+        self.params = ConversionParams::with_calibration(rtd_type, state.calibration)
+            .with_overrides_of(&self.params);
+        self.wiring = state.wiring;
+        self.configure_with(Config { one_shot: false, ..state.config })
+    }
+
+    /// Run a fault detection cycle, with the automatic delay if `settle_us` is `None`
+    /// and the manual one otherwise.
+    fn fault_detection_cycle(&mut self, delay: &mut impl DelayNs, settle_us: Option<u32>)
+        -> Result<FaultStatus, Error<E>>
+    {
+        let auto = self.config & (1 << 6);
+        self.clear_faults()?;
+        if self.config & (1 << 7) == 0 {
+            self.modify_config(0, 1 << 7)?;
+            delay.delay_us(VBIAS_SETTLE_TIME_US);
+        }
+
+        match settle_us {
+            None => {
+                self.modify_config(1 << 6, 0b0100)?;
+            }
+            Some(settle_us) => {
+                self.modify_config(1 << 6, 0b1000)?;
+                delay.delay_us(settle_us);
+                self.modify_config(0, 0b1100)?;
+                delay.delay_us(settle_us);
+            }
+        }
+
+        let mut finished = false;
+        for _ in 0..FAULT_DETECTION_POLLS {
+            delay.delay_us(FAULT_DETECTION_POLL_US);
+            if self.read(Register::CONFIG)? & 0b1100 == 0 {
+                finished = true;
+                break;
+            }
+        }
+        self.modify_config(0, auto)?;
+
+        if !finished {
+            return Err(Error::Timeout);
+        }
+        self.read_fault_status()
+    }
+
+    /// Update the cached configuration, clearing the bits in `clear` and setting the
+    /// bits in `set`, and write it to the device. Returns the written value.
+    fn modify_config(&mut self, clear: u8, set: u8) -> Result<u8, E> {
+        let conf = (self.config & !clear) | set;
+        self.write(Register::CONFIG, conf)?;
+        self.cache_config(conf);
+        Ok(conf)
+    }
+
+    fn cache_config(&mut self, conf: u8) {
+        // the one shot bit isn't cached, but remembered until the RTD is read
+        self.one_shot_pending |= conf & (1 << 5) != 0;
+        self.config = conf & CONFIG_SETTINGS;
+        self.wiring = wiring_for_config(self.wiring, self.config);
+    }
+
+    fn read(&mut self, reg: Register) -> Result<u8, E> {
+        let buffer: [u8; 2] = self.read_many(reg)?;
+        Ok(buffer[1])
+    }
+
+    fn read_many<const N: usize>(&mut self, reg: Register) -> Result<[u8; N], E> {
+        let mut buffer = [0u8; N];
+        buffer[0] = reg.read_address();
+        self.spi.transfer_in_place(&mut buffer)?;
+
+        Ok(buffer)
+    }
+
+    fn read_threshold(&mut self, msb: Register) -> Result<u16, E> {
+        let buffer: [u8; 3] = self.read_many(msb)?;
+        // the threshold registers share the layout of the RTD registers, with
+        // the 15 bit value left shifted by one
+        Ok(raw_to_ratio(((buffer[1] as u16) << 8) | buffer[2] as u16))
+    }
+
+    fn write_threshold(&mut self, msb: Register, ratio: u16) -> Result<(), E> {
+        let val = ratio_to_raw(ratio);
+        self.spi.write(&[msb.write_address(), (val >> 8) as u8, val as u8])?;
+        Ok(())
+    }
+
+    fn write(&mut self, reg: Register, val: u8) -> Result<(), E> {
+        self.spi.write(&[reg.write_address(), val])?;
+        Ok(())
+    }
+}
+
+impl<E, SPI> Max31865<SPI, NoReady>
+where 
+    SPI: SpiDevice<u8, Error = E>,
+{
+    /// Create a new MAX31865 module without a ready pin.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `spi` - The SPI device to communicate on.
+    pub fn new_without_ready(spi: SPI) -> Result<Max31865<SPI, NoReady>, Error<E>> {
+        Max31865::new(spi, NoReady)
+    }
+}
+
+impl<E, SPI, RDY, const R0: u32> Max31865<SPI, RDY, R0>
+where 
+    SPI: SpiDevice<u8, Error = E>,
+    RDY: InputPin
+{
+    /// Determine if a new conversion is available
+    /// 
+    /// # Remarks
+    /// 
+    /// When the module is finished converting the temperature it sets the 
+    /// ready pin to low. It is automatically returned to high upon reading the 
+    /// RTD registers.
+    /// 
+    /// Errors reading the pin are returned, so a failing ready pin can be told apart
+    /// from a conversion that isn't finished. The waiting methods report them as
+    /// `Error::ReadyPin`.
+    pub fn is_ready(&mut self) -> Result<bool, RDY::Error> {
+        self.rdy.is_low()
+    }
 
-        ncs.set_high();
-        let max31865 = Max31865 {
-            spi,
-            ncs,
-            rdy,
-            calibration: default_calib, /* value in ohms multiplied by 100 */
-        };
+    /// Determine if a new conversion is available, returning any error reading the
+    /// ready pin.
+    #[deprecated(note = "use `is_ready`, which returns the pin error now")]
+    pub fn try_is_ready(&mut self) -> Result<bool, RDY::Error> {
+        self.is_ready()
+    }
 
-        Ok(max31865)
+    // `is_ready` with the pin error mapped for the waiting methods
+    fn ready(&mut self) -> Result<bool, Error<E>> {
+        self.is_ready().map_err(|_| Error::ReadyPin)
     }
 
-    /// Updates the devices configuration.
+    /// Wait for a new conversion to become available.
     /// 
-    /// # Arguments 
-    /// * `vbias` - Set to `true` to enable V_BIAS voltage, which is required to correctly perform conversion.Clone
-    /// * `conversion_mode` - `true` to automatically perform conversion, otherwise normally off.
-    /// * `one_shot` - Only perform detection once if set to `true`, otherwise repeats conversion.
-    /// * `sensor_type` - Define whether a two, three or four wire sensor is used.Clone
-    /// * `filter_mode` - Specify the mains frequency that should be used to filter out noise, e.g. 50Hz in Europe.
+    /// # Arguments
     /// 
-    /// # Remarks
+    /// * `timeout_loops` - The maximum number of times the ready pin is polled.
     /// 
-    /// This will update the configuration register of the MAX31865 register. If the device doesn't properly react
-    /// to this, add a delay after calling `new` to increase the time that the chip select line is set high.
+    /// # Remarks
     /// 
-    /// *Note*: The correct sensor configuration also requires changes to the PCB! Make sure to read the datasheet 
-    /// concerning this.
-    pub fn configure(&mut self, vbias: bool, conversion_mode: bool, one_shot: bool,
-        sensor_type: SensorType, filter_mode: FilterMode) -> Result<(), E> {
-        let conf : u8 = ((vbias as u8) << 7) |
-            ((conversion_mode as u8) << 6) |
-            ((one_shot as u8) << 5) |
-            ((sensor_type as u8) << 4) | 
-            (filter_mode as u8);
-
-        self.write(Register::CONFIG, conf)?;
+    /// If the ready pin is not set low within the given number of polls, the
+    /// configuration register is read back, without updating `config`. Returns
+    /// `Error::NotConverting` if V_BIAS or automatic conversion turns out to be
+    /// disabled, which is the usual reason, and `Error::Timeout` otherwise, e.g. if the
+    /// pin isn't connected. A one shot conversion triggered since the RTD registers
+    /// were last read always gives `Error::Timeout`.
+    pub fn wait_for_ready(&mut self, timeout_loops: u32) -> Result<(), Error<E>> {
+        for _ in 0..timeout_loops {
+            if self.ready()? {
+                return Ok(());
+            }
+        }
 
-        Ok(())
+        Err(self.ready_timeout())
     }
 
-    /// Set the calibration reference resistance.
-    /// This can be used to calibrate inaccuracies of both the reference resistor 
-    /// and the PT100 element.
+    /// Wait for a new conversion to become available, polling the ready pin with a
+    /// delay.
     /// 
     /// # Arguments
     /// 
-    /// * `calib` - A 32 bit integer specifying the reference resistance in ohms
-    ///             multiplied by 100, e.g. `40000` for 400 Ohms
+    /// * `delay` - The delay used between polls of the ready pin.
+    /// * `timeout_us` - The maximum time to wait in microseconds.
     /// 
     /// # Remarks
     /// 
-    /// You can perform calibration by putting the sensor in boiling (100 degrees 
-    /// Celcius) water and then measuring the raw value using `read_raw`. Calculate 
-    /// `calib` as `(13851 << 15) / raw >> 1`.
-    pub fn set_calibration(&mut self, calib : u32) -> Result<(), E> {
-        self.calibration = calib;
-        Ok(())
+    /// Unlike `wait_for_ready` the timeout is a time rather than a number of polls, so
+    /// it doesn't depend on the speed of the MCU. The pin is polled every millisecond.
+    /// A conversion takes up to `FilterMode::conversion_time_ms`, so e.g. 100ms covers
+    /// both filters with margin. The errors are the same as for `wait_for_ready`.
+    pub fn wait_for_ready_timeout(&mut self, delay: &mut impl DelayNs, timeout_us: u32)
+        -> Result<(), Error<E>>
+    {
+        // the pin is sampled at least once, even for a timeout of 0
+        for _ in 0..timeout_us.div_ceil(READINGS_POLL_US).max(1) {
+            if self.ready()? {
+                return Ok(());
+            }
+            delay.delay_us(READINGS_POLL_US);
+        }
+
+        Err(self.ready_timeout())
     }
 
-    /// Read the raw resistance value and then perform conversion to degrees Celcius.
+    // diagnose why the ready pin wasn't set low in time, without changing the cache
+    fn ready_timeout(&mut self) -> Error<E> {
+        let bits = match self.read(Register::CONFIG) {
+            Ok(bits) => bits,
+            Err(e) => return Error::Spi(e),
+        };
+        let config = Config::from_bits(bits);
+        if self.one_shot_pending || config.one_shot {
+            Error::Timeout
+        } else if !config.vbias || !config.conversion_mode {
+            Error::NotConverting(config)
+        } else {
+            Error::Timeout
+        }
+    }
+
+    /// Read the converted temperature without blocking.
     /// 
     /// # Remarks
     /// 
-    /// The output value is the value in degrees Celcius multiplied by 100.
-    pub fn read_default_conversion(&mut self) -> Result<u32, E> {
-        let raw = self.read_raw()?;
-        let ohms = ((raw >> 1) as u32 * self.calibration) >> 15;
-        let temp = temp_conversion::lookup_temperature(ohms as u16);
+    /// Returns `nb::Error::WouldBlock` while the ready pin is high, i.e. the conversion
+    /// is still in progress, and the same value as `read_default_conversion` once it
+    /// is ready. This can be polled from a state machine, e.g. with `nb::block!`.
+    #[cfg(feature = "lookup-table")]
+    pub fn read_nb(&mut self) -> nb::Result<i32, Error<E>> {
+        if !self.ready()? {
+            return Err(nb::Error::WouldBlock);
+        }
 
-        Ok(temp)
+        Ok(self.read_default_conversion()?)
     }
 
-    /// Read the raw RTD value.
+    /// Read the converted temperature if a conversion is available.
+    ///
+    /// # Remarks
+    ///
+    /// Returns `None` while the ready pin is high, otherwise the same value as
+    /// `read_default_conversion`. Unlike `read_nb` this doesn't need the `nb` crate
+    /// and fits plain polling loops.
+    #[cfg(feature = "lookup-table")]
+    pub fn read_if_ready(&mut self) -> Result<Option<i32>, Error<E>> {
+        if !self.ready()? {
+            return Ok(None);
+        }
+
+        self.read_default_conversion().map(Some)
+    }
+
+    /// Treat the device as a source of readings in automatic conversion mode.
     /// 
     /// # Remarks
     /// 
-    /// The raw value is the value of the combined MSB and LSB registers.
-    /// The first 15 bits specify the ohmic value in relation to the reference
-    /// resistor (i.e. 2^15 - 1 would be the exact same resistance as the reference
-    /// resistor). See manual for further information.
-    /// The last bit specifies if the conversion was successful. 
-    pub fn read_raw(&mut self) -> Result<u16, E> {
-        let msb : u16 = self.read(Register::RTD_MSB)? as u16;
-        let lsb : u16 = self.read(Register::RTD_LSB)? as u16;
-        
-        Ok((msb << 8) | lsb)
+    /// Automatic conversion has to be enabled, e.g. with `start_continuous`. Each call
+    /// to `Readings::next_reading` then waits for the ready pin and returns the next
+    /// conversion.
+    pub fn readings(&mut self) -> Readings<'_, SPI, RDY, R0> {
+        Readings {
+            max31865: self,
+            timeout_polls: READINGS_POLLS,
+        }
     }
+}
 
-    /// Determine if a new conversion is available
+/// A stream of readings in automatic conversion mode, see `Max31865::readings`.
+#[derive(Debug)]
+pub struct Readings<'a, SPI, RDY, const R0: u32 = 0> {
+    max31865: &'a mut Max31865<SPI, RDY, R0>,
+    timeout_polls: u32,
+}
+
+impl<'a, E, SPI, RDY, const R0: u32> Readings<'a, SPI, RDY, R0>
+where 
+    SPI: SpiDevice<u8, Error = E>,
+    RDY: InputPin
+{
+    /// Set the number of times the ready pin is polled before `next_reading` gives up.
     /// 
     /// # Remarks
     /// 
-    /// When the module is finished converting the temperature it sets the 
-    /// ready pin to low. It is automatically returned to high upon reading the 
-    /// RTD registers.
-    pub fn is_ready(&self) -> Result<bool, E> {
-        Ok(self.rdy.is_low())
+    /// The pin is polled every millisecond, the default of 100 covers the first
+    /// conversion after automatic conversion was enabled.
+    pub fn timeout_polls(mut self, polls: u32) -> Self {
+        self.timeout_polls = polls;
+        self
     }
 
-    fn read(&mut self, reg: Register) -> Result<u8, E> {
-        let buffer: [u8; 2] = self.read_many(reg)?;
-        Ok(buffer[1])
-    }
+    /// Wait for the next conversion and return it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `delay` - The delay used between polls of the ready pin.
+    /// 
+    /// # Remarks
+    /// 
+    /// The ready pin is polled every millisecond, see `timeout_polls`. If no conversion
+    /// is available in time the error is diagnosed like `Max31865::wait_for_ready`,
+    /// i.e. `Error::NotConverting` if V_BIAS or automatic conversion is disabled and
+    /// `Error::Timeout` otherwise. Returns `Error::Fault` if the fault bit of the
+    /// conversion is set.
+    pub fn next_reading(&mut self, delay: &mut impl DelayNs) -> Result<Reading, Error<E>> {
+        for _ in 0..self.timeout_polls {
+            if self.max31865.ready()? {
+                let raw = self.max31865.read_raw()?;
+                if raw & 1 != 0 {
+                    return Err(Error::Fault(self.max31865.read_fault_status()?));
+                }
 
-    fn read_many<B>(&mut self, reg: Register) -> Result<B, E> 
-    where B: Unsize<[u8]>,
-    {
-        let mut buffer: B  = unsafe { mem::zeroed() };
-        {
-            let slice: &mut [u8] = &mut buffer;
-            slice[0] = reg.read_address();
-            self.ncs.set_low();
-            self.spi.transfer(slice)?;
-            self.ncs.set_high();
+                return Ok(self.max31865.params.reading(raw, self.max31865.channel));
+            }
+            delay.delay_us(READINGS_POLL_US);
         }
 
-        Ok(buffer)
+        Err(self.max31865.ready_timeout())
     }
 
-    fn write(&mut self, reg: Register, val: u8) -> Result<(), E> {
-        self.ncs.set_low();
-        self.spi.write(&[reg.write_address(), val])?;
-        self.ncs.set_high();
-        Ok(())
+    /// Turn the readings into an iterator using `delay` between polls of the ready pin.
+    /// 
+    /// # Remarks
+    /// 
+    /// Each item is the result of `next_reading`, which reads the RTD registers and so
+    /// returns the ready pin to high for the next conversion. The iterator never ends,
+    /// errors are yielded as items, e.g. for a quick capture:
+    /// 
+    /// ```ignore
+    /// for reading in max31865.readings().with_delay(&mut delay).take(100) {
+    ///     let reading = reading?;
+    /// }
+    /// ```
+    /// 
+    /// The async driver has no iterator, see `asynch::Readings`.
+    pub fn with_delay<D: DelayNs>(self, delay: &'a mut D) -> ReadingsIter<'a, SPI, RDY, D, R0> {
+        ReadingsIter {
+            readings: self,
+            delay,
+        }
+    }
+}
+
+/// An iterator over the readings in automatic conversion mode, see
+/// `Readings::with_delay`.
+#[derive(Debug)]
+pub struct ReadingsIter<'a, SPI, RDY, D, const R0: u32 = 0> {
+    readings: Readings<'a, SPI, RDY, R0>,
+    delay: &'a mut D,
+}
+
+impl<'a, E, SPI, RDY, D, const R0: u32> Iterator for ReadingsIter<'a, SPI, RDY, D, R0>
+where
+    SPI: SpiDevice<u8, Error = E>,
+    RDY: InputPin,
+    D: DelayNs
+{
+    type Item = Result<Reading, Error<E>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.readings.next_reading(self.delay))
     }
 }
 
 #[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
 #[allow(dead_code)]
 #[derive(Clone, Copy)]
 enum Register {
@@ -218,4 +3414,860 @@ impl Register {
     fn write_address(&self) -> u8 {
         *self as u8 | W
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+    use embedded_hal_mock::eh1::MockError;
+    use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction};
+    use std::io::ErrorKind;
+    use std::vec;
+    use std::vec::Vec;
+
+    // a complete SPI transaction writing `bytes`
+    pub(crate) fn write(bytes: &[u8]) -> Vec<Transaction<u8>> {
+        vec![
+            Transaction::transaction_start(),
+            Transaction::write_vec(bytes.to_vec()),
+            Transaction::transaction_end(),
+        ]
+    }
+
+    // a complete SPI transaction reading the registers from `addr` on, the device
+    // answering with `response`
+    pub(crate) fn read(addr: u8, response: &[u8]) -> Vec<Transaction<u8>> {
+        let mut expected = vec![0; response.len() + 1];
+        expected[0] = addr;
+        let mut returned = vec![0; response.len() + 1];
+        returned[1..].copy_from_slice(response);
+        vec![
+            Transaction::transaction_start(),
+            Transaction::transfer_in_place(expected, returned),
+            Transaction::transaction_end(),
+        ]
+    }
+
+    // a device expecting exactly `transactions`, and a handle to check them with
+    // `done`
+    fn device(transactions: &[Vec<Transaction<u8>>]) -> (Max31865<SpiMock<u8>>, SpiMock<u8>) {
+        let spi = SpiMock::new(transactions.iter().flatten());
+        (Max31865::new_without_ready(spi.clone()).unwrap(), spi)
+    }
+
+    #[test]
+    fn new_doesnt_access_the_bus() {
+        let (max31865, mut spi) = device(&[]);
+        assert_eq!(max31865.config(), Config::empty());
+        spi.done();
+    }
+
+    #[test]
+    fn configure_with_writes_config() {
+        let (mut max31865, mut spi) = device(&[
+            write(&[0x80, 0b1101_0001]),
+            write(&[0x80, 0b1001_0001]),
+        ]);
+        max31865.configure_with(Config::default()).unwrap();
+        assert_eq!(max31865.config(), Config::default());
+        max31865.configure_with(Config::default().conversion_mode(false)).unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn read_raw_assembles_bytes() {
+        let (mut max31865, mut spi) = device(&[read(0x01, &[0x12, 0x35])]);
+        assert_eq!(max31865.read_raw().unwrap(), 0x1235);
+        assert_eq!(max31865.peek_raw(), Some(0x1235));
+        spi.done();
+    }
+
+    #[test]
+    fn set_thresholds_write_shifted_ratio() {
+        let (mut max31865, mut spi) = device(&[
+            write(&[0x83, 0xFF, 0xFE]),
+            write(&[0x85, 0x24, 0x68]),
+        ]);
+        max31865.set_high_fault_threshold(0x7FFF).unwrap();
+        max31865.set_low_fault_threshold(0x1234).unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn read_thresholds_drop_d0() {
+        let (mut max31865, mut spi) = device(&[
+            read(0x03, &[0xFF, 0xFF]),
+            read(0x05, &[0x24, 0x69]),
+        ]);
+        assert_eq!(max31865.read_high_fault_threshold().unwrap(), 0x7FFF);
+        assert_eq!(max31865.read_low_fault_threshold().unwrap(), 0x1234);
+        spi.done();
+    }
+
+    #[test]
+    fn clear_faults_keeps_config() {
+        let (mut max31865, mut spi) = device(&[
+            write(&[0x80, 0b1101_0001]),
+            write(&[0x80, 0b1101_0011]),
+        ]);
+        max31865.configure_with(Config::default()).unwrap();
+        max31865.clear_faults().unwrap();
+        // the fault status clear bit isn't cached
+        assert_eq!(max31865.config(), Config::default());
+        spi.done();
+    }
+
+    #[test]
+    fn run_fault_detection_cycle() {
+        let (mut max31865, mut spi) = device(&[
+            write(&[0x80, 0b1101_0001]),
+            // clear the faults, then start the cycle with automatic conversion paused
+            write(&[0x80, 0b1101_0011]),
+            write(&[0x80, 0b1001_0101]),
+            // D3:D2 read back as 0b01 until the cycle is finished
+            read(0x00, &[0b1001_0101]),
+            read(0x00, &[0b1001_0001]),
+            write(&[0x80, 0b1101_0001]),
+            read(0x07, &[0b0000_1000]),
+        ]);
+        max31865.configure_with(Config::default()).unwrap();
+        let status = max31865.run_fault_detection(&mut NoopDelay::new()).unwrap();
+        assert_eq!(status, FaultStatus::from_bits(0b0000_1000));
+        assert_eq!(max31865.config(), Config::default());
+        spi.done();
+    }
+
+    #[test]
+    fn run_fault_detection_enables_vbias() {
+        let (mut max31865, mut spi) = device(&[
+            write(&[0x80, 0b0000_0010]),
+            write(&[0x80, 0b1000_0000]),
+            write(&[0x80, 0b1000_0100]),
+            read(0x00, &[0b1000_0000]),
+            write(&[0x80, 0b1000_0000]),
+            read(0x07, &[0]),
+        ]);
+        let status = max31865.run_fault_detection(&mut NoopDelay::new()).unwrap();
+        assert!(status.is_ok());
+        spi.done();
+    }
+
+    #[test]
+    fn config_to_bits() {
+        assert_eq!(Config::empty().to_bits(), 0);
+        assert_eq!(Config::empty().vbias(true).to_bits(), 1 << 7);
+        assert_eq!(Config::empty().conversion_mode(true).to_bits(), 1 << 6);
+        assert_eq!(Config::empty().one_shot(true).to_bits(), 1 << 5);
+        assert_eq!(Config::empty().sensor(SensorType::ThreeWire).to_bits(), 1 << 4);
+        assert_eq!(Config::empty().filter(FilterMode::Filter50Hz).to_bits(), 1);
+        assert_eq!(Config::default().to_bits(), 0b1101_0001);
+    }
+
+    #[test]
+    fn config_from_bits_ignores_commands() {
+        for bits in 0..=u8::MAX {
+            assert_eq!(Config::from_bits(bits).to_bits(), bits & 0b1111_0001);
+        }
+    }
+
+    #[test]
+    fn fault_status_from_bits() {
+        assert!(FaultStatus::from_bits(0).is_ok());
+        // D1 and D0 aren't faults
+        assert!(FaultStatus::from_bits(0b11).is_ok());
+
+        let status = FaultStatus::from_bits(1 << 7);
+        assert!(status.rtd_high_threshold && !status.is_ok());
+        assert!(FaultStatus::from_bits(1 << 6).rtd_low_threshold);
+        assert!(FaultStatus::from_bits(1 << 5).refin_high);
+        assert!(FaultStatus::from_bits(1 << 4).refin_low);
+        assert!(FaultStatus::from_bits(1 << 3).rtdin_low);
+        assert!(FaultStatus::from_bits(1 << 2).over_under_voltage);
+
+        let status = FaultStatus::from_bits(0xFF);
+        assert_eq!(status.descriptions().iter().filter(|(active, _)| *active).count(), 6);
+    }
+
+    #[test]
+    fn compute_calibration_from_100c() {
+        // 138.51 Ohms with a 430 Ohm reference is a ratio of 10555
+        assert_eq!(compute_calibration(ratio_to_raw(10555)), 43000);
+        // the fault bit doesn't change the result
+        assert_eq!(compute_calibration(ratio_to_raw(10555) | 1), 43000);
+        // a zero reading doesn't divide by zero
+        assert_eq!(compute_calibration(0), 13851 << 15);
+    }
+
+    // resistances of a PT100 in Ohms from the datasheet and IEC 60751, and the
+    // temperatures in degrees Celcius multiplied by 100
+    const DATASHEET: [(f32, i32); 4] = [
+        (18.52, -20000),
+        (100.0, 0),
+        (138.51, 10000),
+        (390.48, 85000),
+    ];
+
+    // one LSB is about 0.03 degrees Celcius with a 400 Ohm reference, and the
+    // conversion truncates, allow 0.1 degrees Celcius
+    const DATASHEET_TOLERANCE: i32 = 10;
+
+    // the RTD registers for `ohms` with a reference resistance of `reference` Ohms
+    fn raw_for(ohms: f32, reference: f32) -> u16 {
+        ratio_to_raw((ohms / reference * 32768.0 + 0.5) as u16)
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn read_default_conversion_datasheet_points() {
+        for (ohms, temp) in DATASHEET {
+            let raw = raw_for(ohms, 400.0);
+            let (mut max31865, mut spi) = device(&[read(0x01, &raw.to_be_bytes())]);
+            let result = max31865.read_default_conversion().unwrap();
+            assert!((result - temp).abs() <= DATASHEET_TOLERANCE,
+                "{} ohms: {} != {}", ohms, result, temp);
+            spi.done();
+        }
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    fn read_temperature_c_datasheet_points() {
+        for (ohms, temp) in DATASHEET {
+            let raw = raw_for(ohms, 400.0);
+            let (mut max31865, mut spi) = device(&[read(0x01, &raw.to_be_bytes())]);
+            let result = max31865.read_temperature_c().unwrap();
+            let expected = temp as f32 / 100.0;
+            assert!((result - expected).abs() <= DATASHEET_TOLERANCE as f32 / 100.0,
+                "{} ohms: {} != {}", ohms, result, expected);
+            spi.done();
+        }
+    }
+
+    #[test]
+    fn read_resistance_datasheet_points() {
+        for (ohms, _) in DATASHEET {
+            let raw = raw_for(ohms, 400.0);
+            let (mut max31865, mut spi) = device(&[read(0x01, &raw.to_be_bytes())]);
+            let result = max31865.read_resistance().unwrap();
+            // half an LSB of the ratio
+            assert!((result - ohms).abs() <= 400.0 / 65536.0, "{} != {}", result, ohms);
+            spi.done();
+        }
+    }
+
+    #[test]
+    fn read_register_sends_address_first() {
+        let (mut max31865, mut spi) = device(&[
+            read(0x07, &[0xA5]),
+            // the write bit is masked off
+            read(0x00, &[0xD1]),
+        ]);
+        assert_eq!(max31865.read_register(0x07).unwrap(), 0xA5);
+        assert_eq!(max31865.read_register(0x80).unwrap(), 0xD1);
+        spi.done();
+
+        let (mut max31865, mut spi) = device(&[read(0x07, &[0b1000_0100])]);
+        assert_eq!(max31865.read_fault_status().unwrap(), FaultStatus::from_bits(0b1000_0100));
+        spi.done();
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn read_default_conversion_is_signed() {
+        for temp in [-5000, 0, 10000] {
+            let ohms = temp_conversion::temp_to_resistance(temp as f32 / 100.0, 100.0);
+            let raw = raw_for(ohms, 400.0);
+            let (mut max31865, mut spi) = device(&[read(0x01, &raw.to_be_bytes())]);
+            let result = max31865.read_default_conversion().unwrap();
+            assert!((result - temp).abs() <= DATASHEET_TOLERANCE, "{} != {}", result, temp);
+            spi.done();
+        }
+    }
+
+    #[test]
+    fn to_sample_saturates() {
+        let params = ConversionParams::new(RtdType::Pt100);
+        let sample = params.to_sample(0, 0);
+        assert_eq!(sample.saturated, Saturation::Low);
+        assert_eq!(sample.temperature_c, -20000);
+        // 400 Ohm is above the 390.48 Ohm of 850 degrees Celcius
+        let sample = params.to_sample(0xFFFE, 0);
+        assert_eq!(sample.saturated, Saturation::High);
+        assert_eq!(sample.temperature_c, 85000);
+        let sample = params.to_sample(raw_for(100.0, 400.0), 0);
+        assert_eq!(sample.saturated, Saturation::InRange);
+        assert_eq!(sample.temperature_c, 0);
+    }
+
+    #[test]
+    fn set_filter_pauses_conversion() {
+        let (mut max31865, mut spi) = device(&[
+            write(&[0x80, 0b1101_0001]),
+            write(&[0x80, 0b1001_0001]),
+            write(&[0x80, 0b1001_0000]),
+            write(&[0x80, 0b1101_0000]),
+            // without automatic conversion the filter is written directly
+            write(&[0x80, 0b1001_0000]),
+            write(&[0x80, 0b1001_0001]),
+        ]);
+        max31865.configure_with(Config::default()).unwrap();
+        max31865.set_filter(FilterMode::Filter60Hz).unwrap();
+        assert_eq!(max31865.config(), Config::default().filter(FilterMode::Filter60Hz));
+        max31865.stop_continuous().unwrap();
+        max31865.set_filter(FilterMode::Filter50Hz).unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn configure_with_rejects_filter_change() {
+        let (mut max31865, mut spi) = device(&[write(&[0x80, 0b1101_0001])]);
+        max31865.configure_with(Config::default()).unwrap();
+        let config = Config::default().filter(FilterMode::Filter60Hz);
+        assert!(matches!(max31865.configure_with(config), Err(Error::FilterChange)));
+        assert_eq!(max31865.config(), Config::default());
+        spi.done();
+    }
+
+    #[test]
+    fn tolerance_is_symmetric() {
+        assert_eq!(RtdClass::ClassA.tolerance_c(-100.0), RtdClass::ClassA.tolerance_c(100.0));
+        assert_eq!(RtdClass::ClassB.tolerance_c(0.0), Some(0.3));
+        assert_eq!(RtdClass::OutOfTolerance.tolerance_c(0.0), None);
+    }
+
+    #[test]
+    fn classify_sensor() {
+        let pt100 = RtdType::Pt100;
+        let ohms = |celsius| temp_conversion::temp_to_resistance(celsius, 100.0);
+        assert_eq!(pt100.classify(ohms(0.05), 0.0), RtdClass::ThirdDin);
+        assert_eq!(pt100.classify(ohms(-0.12), 0.0), RtdClass::ClassA);
+        assert_eq!(pt100.classify(ohms(0.2), 0.0), RtdClass::ClassB);
+        assert_eq!(pt100.classify(ohms(0.5), 0.0), RtdClass::OutOfTolerance);
+        // the tolerance grows with the distance from 0 degrees Celcius
+        assert_eq!(pt100.classify(ohms(100.2), 100.0), RtdClass::ThirdDin);
+        assert_eq!(pt100.classify(ohms(100.3), 100.0), RtdClass::ClassA);
+    }
+
+    #[test]
+    fn rtd_registers_are_read_in_one_transaction() {
+        // MSB and LSB come from a single 3 byte transfer starting at RTD_MSB
+        let raw = raw_for(100.0, 400.0) | 1;
+        let (mut max31865, mut spi) = device(&[
+            read(0x01, &raw.to_be_bytes()),
+            read(0x01, &raw.to_be_bytes()),
+        ]);
+        let reading = max31865.read_rtd_reading().unwrap();
+        assert_eq!((reading.ratio, reading.fault), (8192, true));
+        let reading = max31865.read_all().unwrap();
+        assert_eq!(reading.raw, raw);
+        assert!(reading.fault);
+        spi.done();
+    }
+
+    #[test]
+    fn fault_status_display() {
+        use std::string::ToString;
+
+        assert_eq!(FaultStatus::from_bits(0).to_string(), "No fault");
+        assert_eq!(FaultStatus::from_bits(1 << 7).to_string(), "RTD High Threshold");
+        assert_eq!(FaultStatus::from_bits((1 << 6) | (1 << 4)).to_string(),
+            "RTD Low Threshold; REFIN- < 0.85 x V_BIAS (FORCE- open)");
+        assert_eq!(FaultStatus::from_bits(0xFF).to_string(), "RTD High Threshold; \
+            RTD Low Threshold; REFIN- > 0.85 x V_BIAS; REFIN- < 0.85 x V_BIAS (FORCE- open); \
+            RTDIN- < 0.85 x V_BIAS (FORCE- open); Overvoltage/Undervoltage");
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn pt1000_calibration_doesnt_overflow() {
+        // 0x7FFF * 430000 doesn't fit a u32
+        assert_eq!(temp_conversion::ratio_to_ohms(0x7FFF, 430000, Rounding::Truncate), 429986);
+
+        // 3904.8 Ohms is 850 degrees Celcius for a PT1000 with a 4300 Ohm reference
+        let raw = raw_for(3904.8, 4300.0);
+        let (mut max31865, mut spi) = device(&[read(0x01, &raw.to_be_bytes())]);
+        max31865.set_rtd_type(RtdType::Pt1000);
+        let result = max31865.read_default_conversion().unwrap();
+        assert!((result - 85000).abs() <= DATASHEET_TOLERANCE, "{} != 85000", result);
+        spi.done();
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[test]
+    fn fault_status_udisplay_matches_display() {
+        use std::string::{String, ToString};
+
+        struct Writer(String);
+
+        impl ufmt::uWrite for Writer {
+            type Error = core::convert::Infallible;
+
+            fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+                self.0.push_str(s);
+                Ok(())
+            }
+        }
+
+        for bits in [0, 1 << 7, 0b0101_0100, 0xFF] {
+            let status = FaultStatus::from_bits(bits);
+            let mut writer = Writer(String::new());
+            ufmt::uwrite!(writer, "{}", status).unwrap();
+            assert_eq!(writer.0, status.to_string());
+        }
+    }
+
+    #[test]
+    fn filter_mode_bit_round_trip() {
+        for filter_mode in [FilterMode::Filter60Hz, FilterMode::Filter50Hz] {
+            assert_eq!(FilterMode::from_bit(filter_mode.to_bit()), filter_mode);
+        }
+        // D0 set selects the 50Hz filter
+        assert!(FilterMode::Filter50Hz.to_bit());
+        assert_eq!(FilterMode::from_bit(false), FilterMode::Filter60Hz);
+    }
+
+    #[test]
+    fn read_config_decodes_filter() {
+        let (mut max31865, mut spi) = device(&[
+            read(0x00, &[0b1101_0001]),
+            read(0x00, &[0b1101_0000]),
+        ]);
+        assert_eq!(max31865.read_config().unwrap().filter_mode, FilterMode::Filter50Hz);
+        assert_eq!(max31865.read_config().unwrap().filter_mode, FilterMode::Filter60Hz);
+        // the cached configuration isn't changed
+        assert_eq!(max31865.config(), Config::empty());
+        spi.done();
+    }
+
+    #[test]
+    fn threshold_temps_out_of_range() {
+        let (mut max31865, mut spi) = device(&[]);
+        assert!(matches!(max31865.set_high_fault_threshold_temp(900.0), Err(Error::OutOfRange)));
+        assert!(matches!(max31865.set_low_fault_threshold_temp(-250.0), Err(Error::OutOfRange)));
+        spi.done();
+    }
+
+    #[test]
+    fn threshold_temps_in_range() {
+        // 150 degrees Celcius is 157.33 Ohms, a ratio of 12888 with a 400 Ohm reference
+        let high = ratio_to_raw(12888).to_be_bytes();
+        let (mut max31865, mut spi) = device(&[
+            write(&[0x83, high[0], high[1]]),
+            write(&[0x85, 0x40, 0x00]),
+        ]);
+        max31865.set_high_fault_threshold_temp(150.0).unwrap();
+        max31865.set_low_fault_threshold_temp(0.0).unwrap();
+        spi.done();
+    }
+
+    #[test]
+    fn conversion_params_references() {
+        let cases = [
+            (RtdType::Pt100, 400.0, 138.51),
+            (RtdType::Pt100, 430.0, 138.51),
+            (RtdType::Pt1000, 4000.0, 1385.1),
+            (RtdType::Pt1000, 4300.0, 1385.1),
+        ];
+        for (rtd_type, reference, ohms) in cases {
+            let params = ConversionParams::with_reference_resistance(rtd_type, reference);
+            let raw = raw_for(ohms, reference);
+            let resistance = params.to_resistance(raw);
+            assert!((resistance - ohms).abs() <= reference / 65536.0, "{} != {}", resistance, ohms);
+            #[cfg(feature = "lookup-table")]
+            {
+                let temp = params.to_temperature(raw);
+                assert!((temp - 10000).abs() <= DATASHEET_TOLERANCE, "{:?}: {} != 10000",
+                    rtd_type, temp);
+            }
+        }
+
+        // the same reading is a lower resistance with a smaller reference
+        let raw = raw_for(138.51, 430.0);
+        let pt100 = ConversionParams::with_reference_resistance(RtdType::Pt100, 400.0);
+        assert!(pt100.to_resistance(raw) < 138.51);
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn one_shot_bit_isnt_cached() {
+        let raw = raw_for(100.0, 400.0);
+        let (mut max31865, mut spi) = device(&[
+            write(&[0x80, 0b1000_0000]),
+            write(&[0x80, 0b1010_0000]),
+            read(0x01, &raw.to_be_bytes()),
+            // a later read-modify-write doesn't trigger another conversion
+            write(&[0x80, 0b1000_0010]),
+        ]);
+        max31865.configure_with(Config::empty().vbias(true)).unwrap();
+        assert_eq!(max31865.read_one_shot(&mut NoopDelay::new()).unwrap(), 0);
+        assert!(!max31865.config().one_shot);
+        max31865.clear_faults().unwrap();
+        spi.done();
+    }
+
+    fn device_with_ready(spi: &[Vec<Transaction<u8>>], rdy: &[PinTransaction])
+        -> (Max31865<SpiMock<u8>, PinMock>, SpiMock<u8>, PinMock)
+    {
+        let spi = SpiMock::new(&spi.concat());
+        let rdy = PinMock::new(rdy);
+        let max31865 = Max31865::new(spi.clone(), rdy.clone()).unwrap();
+        (max31865, spi, rdy)
+    }
+
+    #[test]
+    fn ready_timeout_not_converting() {
+        let (mut max31865, mut spi, mut rdy) = device_with_ready(
+            &[write(&[0x80, 0b1100_0000]), read(0x00, &[0b0100_0000])],
+            &[PinTransaction::get(PinState::High), PinTransaction::get(PinState::High)],
+        );
+        max31865.configure_with(Config::empty().vbias(true).conversion_mode(true)).unwrap();
+        match max31865.wait_for_ready(2) {
+            Err(Error::NotConverting(config)) => assert!(!config.vbias),
+            e => panic!("{:?}", e.map(|_| ())),
+        }
+        // the read back configuration isn't cached
+        assert!(max31865.config().vbias);
+        spi.done();
+        rdy.done();
+    }
+
+    #[test]
+    fn ready_timeout_after_one_shot() {
+        let (mut max31865, mut spi, mut rdy) = device_with_ready(
+            &[write(&[0x80, 0b1010_0000]), read(0x00, &[0b1000_0000])],
+            &[PinTransaction::get(PinState::High)],
+        );
+        // automatic conversion is off, but a conversion was triggered
+        max31865.configure_with(Config::empty().vbias(true).one_shot(true)).unwrap();
+        assert!(matches!(max31865.wait_for_ready(1), Err(Error::Timeout)));
+        spi.done();
+        rdy.done();
+    }
+
+    #[test]
+    fn ready_timeout_one_shot_in_progress() {
+        let (mut max31865, mut spi, mut rdy) = device_with_ready(
+            &[read(0x00, &[0b1010_0000])],
+            &[PinTransaction::get(PinState::High)],
+        );
+        assert!(matches!(max31865.wait_for_ready(1), Err(Error::Timeout)));
+        spi.done();
+        rdy.done();
+    }
+
+    #[test]
+    fn rtd_type_constructors() {
+        let mut spi = SpiMock::new(&[]);
+        let max31865 = Max31865::new_pt100(spi.clone(), NoReady).unwrap();
+        assert_eq!(max31865.conversion_params().rtd_type(), RtdType::Pt100);
+        assert_eq!(max31865.calibration(), 40000);
+
+        let max31865: Max31865Pt1000<_> = Max31865::new_pt1000(spi.clone(), NoReady).unwrap();
+        assert_eq!(max31865.conversion_params().rtd_type(), RtdType::Pt1000);
+        assert_eq!(max31865.calibration(), 430000);
+        spi.done();
+    }
+
+    #[test]
+    fn fixed_rtd_type_survives_restore() {
+        let mut spi = SpiMock::new(&[
+            write(&[0x80, 0x00]),
+            write(&[0x83, 0xFF, 0xFE]),
+            write(&[0x85, 0x00, 0x00]),
+            write(&[0x80, 0x00]),
+        ].concat());
+        let mut max31865: Max31865Pt1000<_> =
+            Max31865::new_pt1000(spi.clone(), NoReady).unwrap();
+        let state = DeviceState {
+            config: Config::empty(),
+            wiring: Wiring::TwoWire,
+            high_fault_threshold: 0x7FFF,
+            low_fault_threshold: 0,
+            rtd_type: RtdType::Pt100,
+            calibration: 400000,
+        };
+
+        max31865.restore(&state).unwrap();
+        assert_eq!(max31865.conversion_params().rtd_type(), RtdType::Pt1000);
+        assert_eq!(max31865.calibration(), 400000);
+        spi.done();
+    }
+
+    #[test]
+    fn run_fault_detection_three_wire() {
+        let (mut max31865, mut spi) = device(&[
+            write(&[0x80, 0b1001_0000]),
+            write(&[0x80, 0b1001_0010]),
+            write(&[0x80, 0b1001_0100]),
+            read(0x00, &[0b1001_0000]),
+            write(&[0x80, 0b1001_0000]),
+            read(0x07, &[0b0010_0000]),
+        ]);
+        max31865.configure_with(Config::empty().vbias(true).sensor(SensorType::ThreeWire))
+            .unwrap();
+        let status = max31865.run_fault_detection(&mut NoopDelay::new()).unwrap();
+        assert!(status.refin_high);
+        assert!(!status.rtdin_low);
+        assert_eq!(max31865.config().sensor_type, SensorType::ThreeWire);
+        spi.done();
+    }
+
+    #[test]
+    fn run_fault_detection_two_wire() {
+        // the same cycle as for three wires, only D4 differs
+        let (mut max31865, mut spi) = device(&[
+            write(&[0x80, 0b1000_0000]),
+            write(&[0x80, 0b1000_0010]),
+            write(&[0x80, 0b1000_0100]),
+            read(0x00, &[0b1000_0000]),
+            write(&[0x80, 0b1000_0000]),
+            read(0x07, &[0]),
+        ]);
+        max31865.configure_with(Config::empty().vbias(true).sensor(SensorType::TwoOrFourWire))
+            .unwrap();
+        assert!(max31865.run_fault_detection(&mut NoopDelay::new()).unwrap().is_ok());
+        spi.done();
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn temperature_offset_shifts_reading() {
+        let raw = raw_for(138.51, 400.0);
+        let (mut max31865, mut spi) = device(&[
+            read(0x01, &raw.to_be_bytes()),
+            read(0x01, &raw.to_be_bytes()),
+        ]);
+        max31865.set_temperature_offset(-50);
+        let result = max31865.read_default_conversion().unwrap();
+        assert!((result - 9950).abs() <= DATASHEET_TOLERANCE, "{} != 9950", result);
+        // the offset survives a change of the calibration
+        max31865.set_calibration(Calibration::from_centi_ohms(40000)).unwrap();
+        assert_eq!(max31865.temperature_offset(), -50);
+        assert_eq!(max31865.read_default_conversion().unwrap(), result);
+        spi.done();
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn set_rounding_nearest() {
+        // 100.0075 Ohms with a 400.03 Ohm reference
+        let raw = ratio_to_raw(8192);
+        let (mut max31865, mut spi) = device(&[
+            read(0x01, &raw.to_be_bytes()),
+            read(0x01, &raw.to_be_bytes()),
+        ]);
+        max31865.set_calibration(Calibration::from_centi_ohms(40003)).unwrap();
+        assert_eq!(max31865.rounding(), Rounding::Truncate);
+        let truncated = max31865.read_default_conversion().unwrap();
+        max31865.set_rounding(Rounding::Nearest);
+        let nearest = max31865.read_default_conversion().unwrap();
+        assert_eq!(truncated, 0);
+        assert!(nearest > truncated, "{} <= {}", nearest, truncated);
+        spi.done();
+    }
+
+    #[test]
+    fn new_with_calibration() {
+        let mut spi = SpiMock::new(&[]);
+        let max31865 = Max31865::new_with_calibration(spi.clone(), NoReady,
+            Calibration::from_ohms(430.0)).unwrap();
+        assert_eq!(max31865.calibration(), 43000);
+        spi.done();
+    }
+
+    #[test]
+    fn is_ready_returns_pin_error() {
+        let (mut max31865, mut spi, mut rdy) = device_with_ready(&[], &[
+            PinTransaction::get(PinState::Low),
+            PinTransaction::get(PinState::High),
+            PinTransaction::get(PinState::High).with_error(MockError::Io(ErrorKind::NotConnected)),
+        ]);
+        assert!(max31865.is_ready().unwrap());
+        assert!(!max31865.is_ready().unwrap());
+        assert!(max31865.is_ready().is_err());
+        spi.done();
+        rdy.done();
+    }
+
+    #[test]
+    fn wait_for_ready_reports_pin_error() {
+        let (mut max31865, mut spi, mut rdy) = device_with_ready(&[], &[
+            PinTransaction::get(PinState::High),
+            PinTransaction::get(PinState::High).with_error(MockError::Io(ErrorKind::NotConnected)),
+        ]);
+        assert!(matches!(max31865.wait_for_ready(5), Err(Error::ReadyPin)));
+        spi.done();
+        rdy.done();
+    }
+
+    #[test]
+    fn new_with_mode() {
+        let mut spi = SpiMock::new(&[]);
+        let max31865 = Max31865::new_without_ready(spi.clone()).unwrap();
+        assert_eq!(max31865.mode(), MODE);
+        let max31865 = Max31865::new_with_mode(spi.clone(), NoReady, MODE_1).unwrap();
+        assert_eq!(max31865.mode(), MODE_1);
+        spi.done();
+    }
+
+    #[test]
+    fn new_with_mode_rejects_modes_0_and_2() {
+        let mut spi = SpiMock::new(&[]);
+        for polarity in [Polarity::IdleLow, Polarity::IdleHigh] {
+            let mode = Mode { phase: Phase::CaptureOnFirstTransition, polarity };
+            assert!(matches!(Max31865::new_with_mode(spi.clone(), NoReady, mode),
+                Err(Error::UnsupportedMode)));
+        }
+        spi.done();
+    }
+
+    #[test]
+    fn raw_ratio_round_trip() {
+        for ratio in [0, 1, 8192, 0x4000, 0x7FFF] {
+            assert_eq!(raw_to_ratio(ratio_to_raw(ratio)), ratio);
+            assert_eq!(ratio_to_raw(ratio) & 1, 0);
+        }
+        for raw in [0, 2, 0x4000, 0xFFFE] {
+            assert_eq!(ratio_to_raw(raw_to_ratio(raw)), raw);
+            // the fault bit is dropped
+            assert_eq!(raw_to_ratio(raw | 1), raw_to_ratio(raw));
+        }
+    }
+
+    #[test]
+    fn fault_bit_is_masked_before_conversion() {
+        let params = ConversionParams::new(RtdType::Pt100);
+        let raw = raw_for(138.51, 400.0);
+        assert_eq!(params.to_resistance(raw | 1), params.to_resistance(raw));
+        #[cfg(feature = "lookup-table")]
+        assert_eq!(params.to_temperature(raw | 1), params.to_temperature(raw));
+        let reading = RtdReading::from_raw(raw | 1);
+        assert_eq!(reading.ratio, raw_to_ratio(raw));
+        assert!(reading.fault);
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn two_point_calibration_corrects_gain_and_offset() {
+        // a 404 Ohm reference and 0.5 Ohms of lead resistance, assumed to be 400 and 0
+        let raw_at = |temp: f32| {
+            raw_for(temp_conversion::temp_to_resistance(temp, 100.0) + 0.5, 404.0)
+        };
+        let raw_50 = raw_at(50.0);
+        let (mut max31865, mut spi) = device(&[
+            read(0x01, &raw_50.to_be_bytes()),
+            read(0x01, &raw_50.to_be_bytes()),
+        ]);
+        let uncorrected = max31865.read_default_conversion().unwrap();
+        assert!((uncorrected - 5000).abs() > 50, "{}", uncorrected);
+
+        max31865.set_two_point_calibration(raw_at(0.0), 0.0, raw_at(100.0), 100.0).unwrap();
+        let corrected = max31865.read_default_conversion().unwrap();
+        assert!((corrected - 5000).abs() <= DATASHEET_TOLERANCE, "{} != 5000", corrected);
+        spi.done();
+    }
+
+    #[test]
+    fn two_point_calibration_rejects_bad_readings() {
+        let mut spi = SpiMock::new(&[]);
+        let mut max31865 = Max31865::new_without_ready(spi.clone()).unwrap();
+        let raw = raw_for(100.0, 400.0);
+        assert!(matches!(max31865.set_two_point_calibration(raw, 0.0, raw, 100.0),
+            Err(Error::OutOfRange)));
+        // a gain far from 1 is a bad measurement rather than a correction
+        assert!(matches!(max31865.set_two_point_calibration(raw, 0.0, raw + 2 * 1000, 100.0),
+            Err(Error::OutOfRange)));
+        spi.done();
+    }
+
+    #[test]
+    fn celsius_to_i16_bounds() {
+        assert_eq!(celsius_to_i16(327.674, 100.0), Some(i16::MAX));
+        assert_eq!(celsius_to_i16(327.676, 100.0), None);
+        assert_eq!(celsius_to_i16(-327.684, 100.0), Some(i16::MIN));
+        assert_eq!(celsius_to_i16(-327.686, 100.0), None);
+        assert_eq!(celsius_to_i16(-0.004, 100.0), Some(0));
+        assert_eq!(celsius_to_i16(-0.006, 100.0), Some(-1));
+        assert_eq!(celsius_to_i16(f32::NAN, 100.0), None);
+        assert_eq!(celsius_to_i16(f32::INFINITY, 10.0), None);
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    fn read_temperature_c_applies_offset() {
+        let raw = raw_for(138.51, 400.0);
+        let (mut max31865, mut spi) = device(&[read(0x01, &raw.to_be_bytes())]);
+        max31865.set_temperature_offset(-50);
+        let result = max31865.read_temperature_c().unwrap();
+        assert!((result - 99.5).abs() <= 0.1, "{} != 99.5", result);
+        spi.done();
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    fn read_temperature_c_uses_custom_conversion() {
+        fn linear(ohms: f32) -> f32 {
+            (ohms - 100.0) / 0.385
+        }
+
+        let raw = raw_for(138.5, 400.0);
+        let (mut max31865, mut spi) = device(&[read(0x01, &raw.to_be_bytes())]);
+        max31865.set_conversion_params(max31865.conversion_params().with_conversion(Some(linear)));
+        let result = max31865.read_temperature_c().unwrap();
+        assert!((result - 100.0).abs() <= 0.05, "{} != 100", result);
+        assert_eq!(max31865.conversion_params().to_temperature_c(raw), result);
+        spi.done();
+    }
+
+    #[test]
+    fn wait_for_ready_timeout_samples_pin_once() {
+        let (mut max31865, mut spi, mut rdy) = device_with_ready(&[],
+            &[PinTransaction::get(PinState::Low)]);
+        max31865.wait_for_ready_timeout(&mut NoopDelay::new(), 0).unwrap();
+        spi.done();
+        rdy.done();
+    }
+
+    fn connection_transactions(readback: [[u8; 2]; 3]) -> Vec<Vec<Transaction<u8>>> {
+        vec![
+            read(0x03, &[0xFF, 0xFE]),
+            write(&[0x83, 0xAA, 0xAA]),
+            read(0x03, &readback[0]),
+            write(&[0x83, 0x55, 0x54]),
+            read(0x03, &readback[1]),
+            write(&[0x83, 0xFF, 0xFE]),
+            read(0x03, &readback[2]),
+        ]
+    }
+
+    #[test]
+    fn detect() {
+        let (mut max31865, mut spi) = device(&connection_transactions(
+            [[0xAA, 0xAA], [0x55, 0x54], [0xFF, 0xFE]]));
+        assert!(max31865.detect().unwrap());
+        spi.done();
+
+        // a missing chip reads back the idle level of MISO
+        let (mut max31865, mut spi) = device(&connection_transactions([[0xFF, 0xFF]; 3]));
+        assert!(!max31865.detect().unwrap());
+        spi.done();
+    }
+
+    #[test]
+    fn set_fault_thresholds_celsius() {
+        let (mut max31865, mut spi) = device(&[
+            write(&[0x85, 0x40, 0x00]),
+            write(&[0x83, 0x64, 0xB0]),
+        ]);
+        max31865.set_fault_thresholds_celsius(0.0, 150.0).unwrap();
+        assert!(matches!(max31865.set_fault_thresholds_celsius(150.0, 0.0),
+            Err(Error::OutOfRange)));
+        spi.done();
+    }
+}
@@ -0,0 +1,204 @@
+//! Plausibility checks for the readings of a device
+//!
+//! EMI can cause single-sample spikes of tens of degrees that the mains filter of the
+//! MAX31865 doesn't remove. `PlausibilityFilter` rejects a temperature that changed
+//! faster than a configured rate since the last valid one and holds the previous
+//! value instead.
+//!
+//! A failing chip or a latched bus can also return the same value over and over,
+//! which none of the fault bits catch. `StuckDetector` flags such frozen values.
+
+/// Rejects implausible temperature jumps between successive samples.
+#[derive(Debug, Clone, Copy)]
+pub struct PlausibilityFilter {
+    max_rate: f32,
+    max_rejections: u8,
+    rejections: u8,
+    last: Option<f32>,
+}
+
+impl PlausibilityFilter {
+    /// Create a new filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_rate` - The largest plausible change in degrees Celcius per sample.
+    /// * `max_rejections` - The number of successive samples that are rejected before a
+    ///                      new value is accepted anyway, so a real step change isn't
+    ///                      held forever.
+    pub fn new(max_rate: f32, max_rejections: u8) -> PlausibilityFilter {
+        PlausibilityFilter {
+            max_rate,
+            max_rejections,
+            rejections: 0,
+            last: None,
+        }
+    }
+
+    /// Feed the next temperature and return the filtered value.
+    ///
+    /// # Remarks
+    ///
+    /// The first sample is always accepted. Afterwards a sample that differs from the
+    /// last valid one by more than `max_rate` is rejected and the last valid value is
+    /// returned instead, until `max_rejections` samples in a row were rejected.
+    pub fn update(&mut self, celsius: f32) -> f32 {
+        match self.last {
+            Some(last) if (celsius - last).abs() > self.max_rate
+                && self.rejections < self.max_rejections =>
+            {
+                self.rejections += 1;
+                last
+            }
+            _ => {
+                self.rejections = 0;
+                self.last = Some(celsius);
+                celsius
+            }
+        }
+    }
+
+    /// Whether the last sample was rejected and the previous value is being held.
+    pub fn is_holding(&self) -> bool {
+        self.rejections > 0
+    }
+
+    /// The last valid temperature, if any sample was accepted yet.
+    pub fn last(&self) -> Option<f32> {
+        self.last
+    }
+
+    /// Forget the last valid temperature, e.g. after the sensor was reconnected.
+    pub fn reset(&mut self) {
+        self.rejections = 0;
+        self.last = None;
+    }
+}
+
+/// Reported by `StuckDetector::update` while the raw value is frozen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StaleData {
+    /// The repeated raw value.
+    pub raw: u16,
+    /// The number of consecutive readings with this value.
+    pub count: u32,
+}
+
+/// Detects raw values that stay bit-identical over many readings.
+#[derive(Debug, Clone, Copy)]
+pub struct StuckDetector {
+    max_repeats: u32,
+    last: Option<u16>,
+    count: u32,
+}
+
+impl StuckDetector {
+    /// Create a new detector.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_repeats` - The number of consecutive identical readings that are still
+    ///                   considered plausible.
+    ///
+    /// # Remarks
+    ///
+    /// The least significant bits of a working ADC practically always change between
+    /// conversions, so a few dozen identical readings already indicate a problem.
+    pub fn new(max_repeats: u32) -> StuckDetector {
+        StuckDetector {
+            max_repeats,
+            last: None,
+            count: 0,
+        }
+    }
+
+    /// Feed the next raw value, e.g. `Reading::raw`, and return `StaleData` if it has
+    /// been the same for more than `max_repeats` readings.
+    pub fn update(&mut self, raw: u16) -> Option<StaleData> {
+        if self.last == Some(raw) {
+            self.count = self.count.saturating_add(1);
+        } else {
+            self.last = Some(raw);
+            self.count = 1;
+        }
+
+        if self.count > self.max_repeats {
+            Some(StaleData { raw, count: self.count })
+        } else {
+            None
+        }
+    }
+
+    /// Forget the previous readings.
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.count = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plausibility_filter_holds_spikes() {
+        let mut filter = PlausibilityFilter::new(1.0, 2);
+        assert_eq!(filter.update(20.0), 20.0);
+        assert_eq!(filter.update(20.5), 20.5);
+        // a spike in either direction is rejected
+        assert_eq!(filter.update(45.0), 20.5);
+        assert!(filter.is_holding());
+        assert_eq!(filter.update(20.8), 20.8);
+        assert_eq!(filter.update(-5.0), 20.8);
+        assert_eq!(filter.update(20.0), 20.0);
+        assert!(!filter.is_holding());
+    }
+
+    #[test]
+    fn plausibility_filter_accepts_step_changes() {
+        let mut filter = PlausibilityFilter::new(1.0, 2);
+        filter.update(20.0);
+        assert_eq!(filter.update(30.0), 20.0);
+        assert_eq!(filter.update(30.0), 20.0);
+        // after `max_rejections` rejections the new value is accepted
+        assert_eq!(filter.update(30.0), 30.0);
+        assert_eq!(filter.last(), Some(30.0));
+    }
+
+    #[test]
+    fn stuck_detector_flags_repeats() {
+        let mut detector = StuckDetector::new(3);
+        for _ in 0..3 {
+            assert_eq!(detector.update(0x4000), None);
+        }
+        assert_eq!(detector.update(0x4000), Some(StaleData { raw: 0x4000, count: 4 }));
+        assert_eq!(detector.update(0x4000), Some(StaleData { raw: 0x4000, count: 5 }));
+    }
+
+    #[test]
+    fn stuck_detector_resets_on_change() {
+        let mut detector = StuckDetector::new(2);
+        detector.update(0x4000);
+        detector.update(0x4000);
+        // a different value starts counting again
+        assert_eq!(detector.update(0x4002), None);
+        assert_eq!(detector.update(0x4002), None);
+        assert_eq!(detector.update(0x4002), Some(StaleData { raw: 0x4002, count: 3 }));
+        detector.reset();
+        assert_eq!(detector.update(0x4002), None);
+    }
+
+    #[test]
+    fn stuck_detector_edge_cases() {
+        // no repeats allowed, so even the first reading is reported
+        let mut detector = StuckDetector::new(0);
+        assert_eq!(detector.update(0x4000), Some(StaleData { raw: 0x4000, count: 1 }));
+        assert_eq!(detector.update(0x4002), Some(StaleData { raw: 0x4002, count: 1 }));
+
+        let mut detector = StuckDetector::new(1);
+        assert_eq!(detector.update(0x4000), None);
+        assert_eq!(detector.update(0x4000), Some(StaleData { raw: 0x4000, count: 2 }));
+        assert_eq!(detector.update(0x4002), None);
+    }
+}
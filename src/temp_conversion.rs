@@ -1,59 +1,418 @@
-//! Temperature conversion table and lookup function
-
-type TempPair = (u16, u16);
-
-// this table contains a pair of temperatures and their 
-// corresponding resistance values for a PT100 thermometer
-// The first entry of each pair is the temperature multiplied by 10,
-// while the second entry contains the temperature at that resistance
-// value multiplied by 10, i.e. at 0 deg C, the probe should have a resistance
-// of 100 ohms
-static LOOKUP_TABLE : &[TempPair]= &[
-    (0, 10000),
-    (1000, 10390),
-    (2000, 10779),
-    (3000, 11167),
-    (4000, 11554),
-    (5000, 11940),
-    (6000, 12324),
-    (7000, 12708),
-    (8000, 13090),
-    (9000, 13471),
-    (10000, 13851),
-    (11000, 14229),
-    (12000, 14607),
-    (13000, 14983),
-];
-
-
-/// Convert the specified PT100 resistance value into a temperature.
-/// 
-/// # Arguments
-/// 
-/// * `val` - A 16 bit unsigned integer specifying the resistance in Ohms multiplied by 100, e.g. 
-///           13851 would indicate 138.51 Ohms and convert to 100 degrees Celcius.
-/// 
-/// # Remarks
-/// 
-/// The output temperature will be in degrees Celcius multiplied by 100, e.g. 10000 would signify 100.00
-/// degrees Celcius.
-/// 
-/// *Note*: This won't handle edge cases very well.
-pub fn lookup_temperature(val : u16) -> u32 {
-    let mut first = &(0, 10000);
-    let mut second = &(1000, 10390);
-    let mut iterator = LOOKUP_TABLE.iter();
-    while let Some(a) = iterator.next() {
-        first = second;
-        second = &a;
-        if a.1 > val { break; }
-    }
-    let second = iterator.next();
-
-    if let Some(second) = second {
-        let temp = (second.0 - first.0) as u32 * (val - first.1) as u32 / (second.1 - first.1) as u32 + first.0 as u32;
-        temp
-    } else {
-        0
-    }
-}
\ No newline at end of file
+//! Temperature conversion table and lookup function
+
+use crate::{ConversionParams, RtdType};
+
+#[cfg(feature = "lookup-table")]
+type TempPair = (i32, u16);
+
+// this table contains a pair of temperatures and their 
+// corresponding resistance values for a PT100 thermometer
+// The first entry of each pair is the temperature multiplied by 100,
+// while the second entry contains the resistance at that temperature
+// multiplied by 100, i.e. at 0 deg C, the probe should have a resistance
+// of 100 ohms. The table is generated by `build.rs` from the Callendar-Van
+// Dusen equation with the IEC 60751 coefficients.
+//
+// The table takes less than a kilobyte of flash, disable the `lookup-table` feature
+// to drop it if only the floating point conversions are used.
+#[cfg(feature = "lookup-table")]
+include!(concat!(env!("OUT_DIR"), "/lookup_table.rs"));
+
+
+/// Convert the specified PT100 resistance value into a temperature.
+/// 
+/// # Arguments
+/// 
+/// * `val` - A 16 bit unsigned integer specifying the resistance in Ohms multiplied by 100, e.g. 
+///           13851 would indicate 138.51 Ohms and convert to 100 degrees Celcius.
+/// 
+/// # Remarks
+/// 
+/// The output temperature will be in degrees Celcius multiplied by 100, e.g. 10000 would signify 100.00
+/// degrees Celcius, and -5000 would signify -50.00 degrees Celcius. The table is binary
+/// searched for the two entries bracketing the resistance, which are then linearly
+/// interpolated.
+/// 
+/// Resistances outside of the table are clamped to the first or last entry, so a
+/// shorted or open sensor reads as the minimum or maximum temperature of the table.
+/// Check the fault bit (see `Max31865::read_rtd`) to detect truly invalid readings.
+#[cfg(feature = "lookup-table")]
+pub fn lookup_temperature(val : u16) -> i32 {
+    let first = LOOKUP_TABLE[0];
+    let last = LOOKUP_TABLE[LOOKUP_TABLE.len() - 1];
+    let val = val.clamp(first.1, last.1);
+
+    // binary search for the first entry above the resistance
+    let upper = LOOKUP_TABLE.partition_point(|a| a.1 <= val)
+        .clamp(1, LOOKUP_TABLE.len() - 1);
+    let (first, second) = (LOOKUP_TABLE[upper - 1], LOOKUP_TABLE[upper]);
+
+    (second.0 - first.0) * (val as i32 - first.1 as i32) / (second.1 - first.1) as i32 + first.0
+}
+
+/// The range of temperatures in degrees Celcius supported by the conversions.
+/// 
+/// # Remarks
+/// 
+/// This is the -200 to 850 degrees Celcius range the Callendar-Van Dusen equation is
+/// specified for. The lookup table generated by `build.rs` covers the same range, so
+/// the range doesn't depend on the enabled features.
+pub fn temperature_range() -> (f32, f32) {
+    (-200.0, 850.0)
+}
+
+/// Convert the specified temperature into a PT100 resistance value.
+/// 
+/// # Arguments
+/// 
+/// * `temp` - The temperature in degrees Celcius multiplied by 100, e.g. 10000 for
+///            100 degrees Celcius.
+/// 
+/// # Remarks
+/// 
+/// The output resistance will be in Ohms multiplied by 100. This is the inverse of
+/// `lookup_temperature` and linearly interpolates between the table entries.
+/// Temperatures outside of the table are clamped to the first or last entry.
+#[cfg(feature = "lookup-table")]
+pub fn lookup_resistance(temp: i32) -> u16 {
+    let first = LOOKUP_TABLE[0];
+    let last = LOOKUP_TABLE[LOOKUP_TABLE.len() - 1];
+    let temp = temp.clamp(first.0, last.0);
+
+    let upper = LOOKUP_TABLE.partition_point(|a| a.0 <= temp)
+        .clamp(1, LOOKUP_TABLE.len() - 1);
+    let (first, second) = (LOOKUP_TABLE[upper - 1], LOOKUP_TABLE[upper]);
+
+    ((second.1 - first.1) as i32 * (temp - first.0) / (second.0 - first.0) + first.1 as i32) as u16
+}
+
+/// Convert a raw PT100 reading into a temperature without a device.
+/// 
+/// # Arguments
+/// 
+/// * `raw` - The combined RTD registers as returned by `Max31865::read_raw`.
+/// * `calibration` - The calibration value, i.e. the reference resistance in Ohms
+///                   multiplied by 100, see `Max31865::set_calibration`.
+/// 
+/// # Remarks
+/// 
+/// This applies exactly the same math as `Max31865::read_default_conversion` for a
+/// PT100, so values logged on the device can be converted later, e.g. on a host. The
+/// output temperature is in degrees Celcius multiplied by 100. The fault bit is
+/// ignored.
+#[cfg(feature = "lookup-table")]
+pub fn raw_to_temp(raw: u16, calibration: u32) -> i32 {
+    ratio_to_temp(crate::raw_to_ratio(raw), calibration, 100)
+}
+
+/// Convert a temperature into the raw PT100 reading the device would report.
+/// 
+/// # Arguments
+/// 
+/// * `celsius` - The temperature in degrees Celcius.
+/// * `calibration` - The calibration value, i.e. the reference resistance in Ohms
+///                   multiplied by 100, see `Max31865::set_calibration`.
+/// 
+/// # Remarks
+/// 
+/// This is the inverse of `raw_to_temp`, and returns the combined RTD registers, i.e.
+/// the 15 bit ratio shifted left by one with the fault bit clear, so synthetic
+/// temperatures can be fed through the conversions without hardware. It uses the same
+/// math as `Max31865::set_high_fault_threshold_temp`. Temperatures outside of
+/// `temperature_range` are clamped.
+pub fn temp_to_raw(celsius: f32, calibration: u32) -> u16 {
+    let (min, max) = temperature_range();
+    let params = ConversionParams::with_calibration(RtdType::Pt100, calibration);
+    crate::ratio_to_raw(params.temp_to_ratio(celsius.clamp(min, max)).unwrap_or(0x7FFF))
+}
+
+/// Convert a 15 bit ratio to degrees Celcius multiplied by 100 for an element with a
+/// nominal resistance of `r0` Ohms.
+#[cfg(feature = "lookup-table")]
+pub(crate) fn ratio_to_temp(ratio: u16, calibration: u32, r0: u32) -> i32 {
+    ohms_to_temp(ratio_to_ohms(ratio, calibration, crate::Rounding::Truncate), r0)
+}
+
+/// Convert a 15 bit ratio to the resistance in Ohms multiplied by 100.
+#[cfg(feature = "lookup-table")]
+pub(crate) fn ratio_to_ohms(ratio: u16, calibration: u32, rounding: crate::Rounding) -> u64 {
+    let half = match rounding {
+        crate::Rounding::Truncate => 0,
+        crate::Rounding::Nearest => 1 << 14,
+    };
+    // a 15 bit ratio times a PT1000 calibration (e.g. 0x7FFF * 430000) overflows a
+    // u32, so the multiplication is done in 64 bits
+    (ratio as u64 * calibration as u64 + half) >> 15
+}
+
+/// Convert a resistance in Ohms multiplied by 100 to degrees Celcius multiplied by 100
+/// for an element with a nominal resistance of `r0` Ohms.
+#[cfg(feature = "lookup-table")]
+pub(crate) fn ohms_to_temp(ohms: u64, r0: u32) -> i32 {
+    // scale a PT1000 down to the PT100 range of the table
+    let ohms = ohms * 100 / r0 as u64;
+    lookup_temperature(ohms.min(u16::MAX as u64) as u16)
+}
+
+// Callendar-Van Dusen coefficients as specified by IEC 60751
+const CVD_A: f32 = 3.9083e-3;
+const CVD_B: f32 = -5.775e-7;
+const CVD_C: f32 = -4.183e-12;
+
+/// Convert a platinum RTD resistance into a temperature using the Callendar-Van Dusen
+/// equation.
+/// 
+/// # Arguments
+/// 
+/// * `ohms` - The resistance of the element in Ohms.
+/// * `r0` - The nominal resistance of the element at 0 degrees Celcius, e.g. `100.0`
+///          for a PT100 or `1000.0` for a PT1000.
+/// 
+/// # Remarks
+/// 
+/// The output temperature is in degrees Celcius. Above 0 degrees Celcius the equation
+/// is a quadratic and solved directly. Below 0 degrees Celcius the quadratic solution
+/// is refined with a few Newton iterations including the C term. Requires the `float`
+/// feature.
+/// 
+/// Like `lookup_temperature`, resistances outside of `temperature_range` are clamped,
+/// so e.g. a shorted sensor reads -200 degrees Celcius and an open one 850 degrees
+/// Celcius rather than an extrapolated value or NaN. Check the fault bit to detect
+/// truly invalid readings.
+#[cfg(feature = "float")]
+pub fn resistance_to_temp(ohms: f32, r0: f32) -> f32 {
+    let (min, max) = temperature_range();
+    let ohms = ohms.clamp(temp_to_resistance(min, r0), temp_to_resistance(max, r0));
+    let ratio = ohms / r0;
+    let temp = (-CVD_A + libm::sqrtf(CVD_A * CVD_A - 4.0 * CVD_B * (1.0 - ratio))) / (2.0 * CVD_B);
+    if ratio >= 1.0 {
+        return temp;
+    }
+
+    let mut temp = temp;
+    for _ in 0..4 {
+        let t2 = temp * temp;
+        let f = 1.0 + CVD_A * temp + CVD_B * t2 + CVD_C * (temp - 100.0) * t2 * temp - ratio;
+        let df = CVD_A + 2.0 * CVD_B * temp + CVD_C * (4.0 * t2 * temp - 300.0 * t2);
+        temp -= f / df;
+    }
+    temp
+}
+
+/// Convert a temperature into a platinum RTD resistance using the Callendar-Van Dusen
+/// equation.
+/// 
+/// # Arguments
+/// 
+/// * `celsius` - The temperature in degrees Celcius.
+/// * `r0` - The nominal resistance of the element at 0 degrees Celcius, e.g. `100.0`
+///          for a PT100 or `1000.0` for a PT1000.
+/// 
+/// # Remarks
+/// 
+/// The output resistance is in Ohms. This is the inverse of `resistance_to_temp`, and
+/// can be used to compute fault thresholds from temperature limits.
+pub fn temp_to_resistance(celsius: f32, r0: f32) -> f32 {
+    let t2 = celsius * celsius;
+    let c = if celsius < 0.0 { CVD_C * (celsius - 100.0) * t2 * celsius } else { 0.0 };
+    r0 * (1.0 + CVD_A * celsius + CVD_B * t2 + c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // IEC 60751 resistances of a PT100 in Ohms multiplied by 100 and the temperatures
+    // in degrees Celcius multiplied by 100
+    const REFERENCE: [(u16, i32); 6] = [
+        (1852, -20000),
+        (10000, 0),
+        (13851, 10000),
+        (17586, 20000),
+        (31371, 60000),
+        (39048, 85000),
+    ];
+
+    // the table is interpolated between entries 10 degrees Celcius apart and the
+    // resistances are rounded to 0.01 Ohms, allow 0.05 degrees Celcius
+    #[cfg(feature = "lookup-table")]
+    const TOLERANCE: i32 = 5;
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn lookup_temperature_matches_reference() {
+        for (ohms, temp) in REFERENCE {
+            let result = lookup_temperature(ohms);
+            assert!((result - temp).abs() <= TOLERANCE, "{} ohms: {} != {}", ohms, result, temp);
+        }
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn lookup_table_covers_range() {
+        let (min, max) = temperature_range();
+        assert_eq!(LOOKUP_TABLE[0].0, (min * 100.0) as i32);
+        assert_eq!(LOOKUP_TABLE[LOOKUP_TABLE.len() - 1].0, (max * 100.0) as i32);
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    fn resistance_to_temp_matches_reference() {
+        for (ohms, temp) in REFERENCE {
+            let result = resistance_to_temp(ohms as f32 / 100.0, 100.0);
+            let expected = temp as f32 / 100.0;
+            assert!((result - expected).abs() <= 0.05, "{} ohms: {} != {}", ohms, result, expected);
+        }
+    }
+
+    #[test]
+    fn temp_to_resistance_matches_reference() {
+        for (ohms, temp) in REFERENCE {
+            let result = temp_to_resistance(temp as f32 / 100.0, 100.0);
+            let expected = ohms as f32 / 100.0;
+            assert!((result - expected).abs() <= 0.01, "{} C: {} != {}", temp, result, expected);
+        }
+    }
+
+    #[test]
+    fn temp_to_raw_high_range() {
+        let at_130 = temp_to_raw(130.0, 43000);
+        assert_ne!(temp_to_raw(150.0, 43000), at_130);
+        assert_ne!(temp_to_raw(850.0, 43000), at_130);
+        assert!(temp_to_raw(850.0, 43000) > temp_to_raw(150.0, 43000));
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn lookup_table_is_monotonic() {
+        for pair in LOOKUP_TABLE.windows(2) {
+            assert!(pair[0].0 < pair[1].0);
+            assert!(pair[0].1 < pair[1].1);
+        }
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn lookup_resistance_inverts_lookup_temperature() {
+        for temp in (-20000..=85000).step_by(1250) {
+            let result = lookup_temperature(lookup_resistance(temp));
+            assert!((result - temp).abs() <= TOLERANCE, "{} != {}", result, temp);
+        }
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn raw_to_temp_inverts_temp_to_raw() {
+        // one LSB of the ratio is about 0.04 degrees Celcius with a 430 Ohm reference
+        for celsius in (-200..=850).step_by(25) {
+            let raw = temp_to_raw(celsius as f32, 43000);
+            assert_eq!(raw & 1, 0);
+            let result = raw_to_temp(raw, 43000);
+            assert!((result - celsius * 100).abs() <= 10, "{} != {}", result, celsius * 100);
+        }
+    }
+
+    #[test]
+    fn temp_to_raw_clamps_to_range() {
+        assert_eq!(temp_to_raw(-300.0, 43000), temp_to_raw(-200.0, 43000));
+        assert_eq!(temp_to_raw(1000.0, 43000), temp_to_raw(850.0, 43000));
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn ratio_to_ohms_scales_by_calibration() {
+        assert_eq!(ratio_to_ohms(0, 40000, crate::Rounding::Truncate), 0);
+        assert_eq!(ratio_to_ohms(16384, 40000, crate::Rounding::Truncate), 20000);
+        assert_eq!(ratio_to_ohms(8192, 43000, crate::Rounding::Truncate), 10750);
+        // 3 * 40000 / 32768 = 3.66
+        assert_eq!(ratio_to_ohms(3, 40000, crate::Rounding::Truncate), 3);
+        assert_eq!(ratio_to_ohms(3, 40000, crate::Rounding::Nearest), 4);
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn ratio_to_ohms_rounds_at_half_lsb() {
+        // 8192 * 40001 / 32768 = 10000.25, just above a boundary
+        assert_eq!(ratio_to_ohms(8192, 40001, crate::Rounding::Truncate), 10000);
+        assert_eq!(ratio_to_ohms(8192, 40001, crate::Rounding::Nearest), 10000);
+        // 10000.5 rounds up
+        assert_eq!(ratio_to_ohms(8192, 40002, crate::Rounding::Truncate), 10000);
+        assert_eq!(ratio_to_ohms(8192, 40002, crate::Rounding::Nearest), 10001);
+        // 10000.75, just below the next boundary
+        assert_eq!(ratio_to_ohms(8192, 40003, crate::Rounding::Truncate), 10000);
+        assert_eq!(ratio_to_ohms(8192, 40003, crate::Rounding::Nearest), 10001);
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn ohms_to_temp_scales_pt1000() {
+        assert_eq!(ohms_to_temp(100000, 1000), 0);
+        assert_eq!(ohms_to_temp(138510, 1000), ohms_to_temp(13851, 100));
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    fn resistance_to_temp_inverts_temp_to_resistance() {
+        for r0 in [100.0, 1000.0] {
+            for celsius in (-200..=850).step_by(50) {
+                let celsius = celsius as f32;
+                let result = resistance_to_temp(temp_to_resistance(celsius, r0), r0);
+                assert!((result - celsius).abs() <= 0.01, "{} != {}", result, celsius);
+            }
+        }
+    }
+
+    #[cfg(feature = "float")]
+    #[test]
+    fn resistance_to_temp_clamps_to_range() {
+        for (ohms, r0, expected) in [(0.0, 100.0, -200.0), (1000.0, 100.0, 850.0),
+            (10000.0, 1000.0, 850.0)] {
+            let result = resistance_to_temp(ohms, r0);
+            assert!((result - expected).abs() <= 0.01, "{} ohms: {} != {}", ohms, result, expected);
+        }
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn lookup_temperature_halfway_between_entries() {
+        for pair in LOOKUP_TABLE.windows(2) {
+            let ohms = ((pair[0].1 as u32 + pair[1].1 as u32) / 2) as u16;
+            let expected = (pair[0].0 + pair[1].0) / 2;
+            let result = lookup_temperature(ohms);
+            assert!((result - expected).abs() <= 2, "{} ohms: {} != {}", ohms, result, expected);
+        }
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn lookup_temperature_interpolates() {
+        // 20 and 30 degrees Celcius are 107.79 and 111.67 Ohms
+        assert_eq!(lookup_temperature(10779), 2000);
+        assert_eq!(lookup_temperature(10876), 2250);
+        assert_eq!(lookup_temperature(10973), 2500);
+        assert_eq!(lookup_temperature(11070), 2750);
+        assert_eq!(lookup_temperature(11167), 3000);
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn lookup_temperature_is_monotonic() {
+        let mut previous = lookup_temperature(0);
+        for ohms in 1..=u16::MAX {
+            let temp = lookup_temperature(ohms);
+            assert!(temp >= previous, "{} ohms: {} < {}", ohms, temp, previous);
+            previous = temp;
+        }
+    }
+
+    #[cfg(feature = "lookup-table")]
+    #[test]
+    fn lookup_temperature_clamps_to_table() {
+        assert_eq!(lookup_temperature(0), LOOKUP_TABLE[0].0);
+        assert_eq!(lookup_temperature(u16::MAX), LOOKUP_TABLE[LOOKUP_TABLE.len() - 1].0);
+        assert_eq!(lookup_resistance(i32::MIN), LOOKUP_TABLE[0].1);
+        assert_eq!(lookup_resistance(i32::MAX), LOOKUP_TABLE[LOOKUP_TABLE.len() - 1].1);
+        // a PT1000 far above the table doesn't wrap
+        assert_eq!(ohms_to_temp(u64::MAX / 100, 1000), 85000);
+    }
+}